@@ -0,0 +1,60 @@
+//! Benchmarks of parsing a game from its API responses and preprocessing saved games, on the test fixtures.
+//! Run them with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use mlb_transformer::model::{Game, ParseOptions, Preprocess, PreprocessOptions};
+
+/// Answer requests only from the fixture cache, so the benchmarks measure parsing rather than the network.
+fn offline_api() {
+    mlb_transformer::api::configure(mlb_transformer::api::ApiConfig {
+        cache_dir: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/cache").into()),
+        offline: true,
+        ..Default::default()
+    });
+}
+
+/// The JSON in `tests/fixtures/games/{name}.json`.
+fn fixture(name: &str) -> serde_json::Value {
+    let path = format!("{}/tests/fixtures/games/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn parse(c: &mut Criterion) {
+    offline_api();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (feed, boxscore) = (fixture("feed"), fixture("boxscore"));
+    let options = ParseOptions::default();
+
+    c.bench_function("parse feed and boxscore", |b| {
+        b.to_async(&runtime).iter(|| Game::from_feed_and_boxscore(&feed, &boxscore, &options))
+    });
+}
+
+fn preprocess(c: &mut Criterion) {
+    offline_api();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let feed_game = || runtime.block_on(Game::from_feed_and_boxscore(&fixture("feed"), &fixture("boxscore"), &ParseOptions::default())).unwrap();
+    let saved_game = || Game::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/games/unversioned.json")).unwrap();
+    let games = [feed_game(), saved_game()];
+
+    let mut group = c.benchmark_group("preprocess");
+    group.throughput(Throughput::Elements(games.len() as u64));
+    let all_lines = PreprocessOptions {
+        descriptions: true,
+        pitches: true,
+        at_bats: true,
+        run_expectancy: Some(Default::default()),
+        ..Default::default()
+    };
+    for (name, options) in [("default", PreprocessOptions::default()), ("all lines", all_lines)] {
+        group.bench_function(name, |b| b.iter(|| games.iter().map(|game| game.preprocess(&options)).collect::<Vec<String>>()));
+    }
+    // what `token-stats` does with each game
+    group.bench_function("count tokens", |b| {
+        b.iter(|| games.iter().map(|game| game.preprocess(&PreprocessOptions::default()).split_whitespace().count()).sum::<usize>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, preprocess);
+criterion_main!(benches);
@@ -0,0 +1,152 @@
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const USER_AGENT: &str = concat!("mlb-transformer/", env!("CARGO_PKG_VERSION"));
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+const DEFAULT_MAX_RPS: NonZeroU32 = NonZeroU32::new(10).unwrap();
+
+static API: OnceLock<Api> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct ApiConfig {
+    /// How long to wait for a connection to be established.
+    pub connect_timeout: Duration,
+    /// How long to wait for a whole request, including reading the body.
+    pub timeout: Duration,
+    /// Where to record raw responses, keyed by url. Responses are not cached if this is `None`.
+    pub cache_dir: Option<PathBuf>,
+    /// Read responses only from the cache, never from the network.
+    pub offline: bool,
+    /// The most requests to send over the network per second, across all concurrent requests.
+    pub max_rps: NonZeroU32,
+}
+
+struct Api {
+    client: reqwest::Client,
+    /// Every request (including retries) waits for this, so requests are spread out to at most `max_rps` per second.
+    rate_limiter: governor::DefaultDirectRateLimiter,
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            timeout: Duration::from_secs(30),
+            cache_dir: None,
+            offline: false,
+            max_rps: DEFAULT_MAX_RPS,
+        }
+    }
+}
+
+impl Api {
+    fn new(config: ApiConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.timeout)
+            .build()
+            .unwrap();
+
+        Self {
+            client,
+            rate_limiter: governor::RateLimiter::direct(governor::Quota::per_second(config.max_rps)),
+            cache_dir: config.cache_dir,
+            offline: config.offline,
+        }
+    }
+}
+
+/// Configure the shared client. Must be called before the first request to take effect.
+pub fn configure(config: ApiConfig) {
+    let _ = API.set(Api::new(config));
+}
+
+fn api() -> &'static Api {
+    API.get_or_init(|| Api::new(ApiConfig::default()))
+}
+
+/// The file a response from the given url is cached in.
+fn cache_path(cache_dir: &std::path::Path, url: &str) -> PathBuf {
+    let key = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect::<String>();
+
+    cache_dir.join(format!("{key}.json"))
+}
+
+/// How long to wait before retrying a rate-limited request, from its `Retry-After` header
+/// if it gives a number of seconds, otherwise an exponential backoff.
+fn retry_delay(response: &reqwest::Response, attempt: u32) -> Duration {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| Duration::from_secs(2u64.pow(attempt)))
+}
+
+/// Get the JSON body at the given url, from the cache if it has been recorded there,
+/// otherwise using the shared client (recording the response in the cache if enabled).
+pub async fn get_json(url: &str) -> Result<serde_json::Value, String> {
+    let api = api();
+
+    let cache_path = api.cache_dir.as_ref().map(|cache_dir| cache_path(cache_dir, url));
+    if let Some(cache_path) = &cache_path {
+        if let Ok(cached) = std::fs::read_to_string(cache_path) {
+            return serde_json::from_str(&cached).map_err(|e| format!("Failed to parse cached response: {url}: {e}"));
+        }
+    }
+    if api.offline {
+        return Err(format!("Response not in cache (offline): {url}"));
+    }
+
+    let value = fetch_json(api, url).await?;
+
+    if let Some(cache_path) = &cache_path {
+        if let Err(e) = crate::storage::write_atomic(cache_path, value.to_string()) {
+            tracing::warn!(%url, error = %e, "Failed to cache response");
+        }
+    }
+
+    Ok(value)
+}
+
+/// Get the JSON body at the given url over the network, retrying when rate-limited.
+async fn fetch_json(api: &Api, url: &str) -> Result<serde_json::Value, String> {
+    let mut attempt = 0;
+    let response = loop {
+        api.rate_limiter.until_ready().await;
+        let response = match api.client.get(url).send().await {
+            Ok(response) => response,
+            Err(e) if e.is_timeout() => return Err(format!("Request timed out (retryable): {url}")),
+            Err(e) => return Err(format!("Request failed: {url}: {e}")),
+        };
+
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            break response;
+        }
+        if attempt == MAX_RATE_LIMIT_RETRIES {
+            return Err(format!("Rate limited (retryable): {url}"));
+        }
+
+        let delay = retry_delay(&response, attempt);
+        tracing::warn!(%url, attempt, delay_secs = delay.as_secs(), "Rate limited, retrying");
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    };
+
+    match response.json::<serde_json::Value>().await {
+        Ok(value) => Ok(value),
+        Err(e) if e.is_timeout() => Err(format!("Request timed out (retryable): {url}")),
+        Err(e) => Err(format!("Failed to parse response: {url}: {e}")),
+    }
+}
@@ -0,0 +1,7 @@
+//! Gathering and preprocessing MLB play-by-play data. The `mlb-transformer` binary is the command line interface;
+//! the library exists so that benchmarks can use the same code.
+
+pub mod api;
+pub mod model;
+pub mod run_expectancy;
+pub mod storage;
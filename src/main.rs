@@ -1,35 +1,959 @@
-use glob::glob;
-use indicatif::{ProgressIterator, ProgressStyle};
+use std::io::{IsTerminal, Write};
 
-mod model;
+use futures::StreamExt;
+use indicatif::{ProgressBar, ProgressIterator, ProgressStyle};
+use tracing_subscriber::{filter::LevelFilter, prelude::*, EnvFilter};
 
-use model::Preprocess;
+use mlb_transformer::{api, model, run_expectancy, storage};
 
-// all mlb team ids
-const TEAM_IDS: [u8; 30] = [108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 133, 134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 158];
+use model::{Preprocess, PreprocessOptions};
 
-/// Remove the completed team from the list of teams to be processed in the given season.
-fn save_progress(season: u16, completed_team_id: u8) {
+// how many games are fetched at once unless `--concurrency` is given
+const DEFAULT_CONCURRENCY: usize = 4;
+
+// the number of regular season games each mlb team plays, which `report` expects unless `--expected` is given
+const DEFAULT_EXPECTED_GAMES: usize = 162;
+
+// the share of the expected games a team can have saved before `report` flags it
+const REPORT_THRESHOLD: f64 = 0.9;
+
+// how often progress is printed as a line of text when stderr is not a terminal
+const PROGRESS_LINE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+// all mlb team ids, used when the season's teams cannot be fetched offline
+const TEAM_IDS: [u32; 30] = [108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 133, 134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 158];
+
+/// Log to stderr (warnings and above, unless overridden by `RUST_LOG`) and to a daily-rotated `data/log.txt`.
+fn init_logging() {
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")));
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_writer(tracing_appender::rolling::daily("data", "log.txt"))
+        .with_ansi(false)
+        .with_filter(LevelFilter::INFO);
+
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(file_layer)
+        .init();
+}
+
+/// Get the value following the flag `name` on the command line, if present.
+fn flag_value(name: &str) -> Option<String> {
+    let args = std::env::args().collect::<Vec<String>>();
+    let index = args.iter().position(|arg| arg == name)?;
+
+    args.get(index + 1).cloned()
+}
+
+/// Whether the flag `name` was given on the command line.
+fn has_flag(name: &str) -> bool {
+    std::env::args().any(|arg| arg == name)
+}
+
+/// Build the API configuration from the `--connect-timeout` and `--timeout` flags (in seconds),
+/// and the `--cache`, `--cache-dir` and `--offline` flags.
+fn api_config_from_args() -> api::ApiConfig {
+    let mut config = api::ApiConfig::default();
+    if let Some(secs) = flag_value("--connect-timeout") {
+        config.connect_timeout = std::time::Duration::from_secs(secs.parse().expect("--connect-timeout must be a number of seconds"));
+    }
+    if let Some(secs) = flag_value("--timeout") {
+        config.timeout = std::time::Duration::from_secs(secs.parse().expect("--timeout must be a number of seconds"));
+    }
+    if let Some(cache_dir) = flag_value("--cache-dir") {
+        config.cache_dir = Some(cache_dir.into());
+    } else if has_flag("--cache") || has_flag("--offline") {
+        config.cache_dir = Some("data/cache".into());
+    }
+    config.offline = has_flag("--offline");
+    if let Some(max_rps) = flag_value("--max-rps") {
+        config.max_rps = max_rps.parse().expect("--max-rps must be a positive number");
+    }
+
+    config
+}
+
+/// Build the parsing options from the `--live`, `--min-completeness`, `--optional-boxscore` and `--save-raw` flags.
+fn parse_options_from_args() -> model::ParseOptions {
+    model::ParseOptions {
+        allow_live: has_flag("--live"),
+        min_completeness: flag_value("--min-completeness").map(|fraction| fraction.parse().expect("--min-completeness must be a fraction between 0 and 1")),
+        optional_boxscore: has_flag("--optional-boxscore"),
+        save_raw: has_flag("--save-raw"),
+    }
+}
+
+/// Build the preprocessing options from the `--player-ids`, `--descriptions`, `--re24` and `--re24-matrix` flags,
+/// the `--lowercase`, `--short-keys` and `--key-names` flags,
+/// the `--include-types` and `--exclude-types` flags (comma-separated play types),
+/// and the `--anonymize`, `--anonymize-seed` and `--anonymize-scope` (`game` or `global`) flags.
+fn preprocess_options_from_args() -> PreprocessOptions {
+    let mut options = PreprocessOptions {
+        player_ids: has_flag("--player-ids"),
+        descriptions: has_flag("--descriptions"),
+        pitches: has_flag("--pitches"),
+        at_bats: has_flag("--at-bats"),
+        outcomes: has_flag("--outcomes"),
+        alignment: has_flag("--alignment"),
+        no_movements: has_flag("--no-movements"),
+        lowercase: has_flag("--lowercase"),
+        compact: has_flag("--compact"),
+        ..Default::default()
+    };
+    options.fielders = match flag_value("--fielders").as_deref() {
+        None | Some("names") => model::FielderFormat::Names,
+        Some("positions") => model::FielderFormat::Positions,
+        Some("both") => model::FielderFormat::Both,
+        Some(format) => panic!("Unknown fielder format: {format}"),
+    };
+    options.formatter = match flag_value("--output-format").as_deref() {
+        None | Some("json") => None,
+        Some("text") => Some(std::sync::Arc::new(model::TextFormatter)),
+        Some(format) => {
+            eprintln!("Unknown output format: {format}; expected json or text.");
+            std::process::exit(1);
+        },
+    };
+    if let Some(path) = flag_value("--re24-matrix") {
+        options.run_expectancy = Some(run_expectancy::RunExpectancy::from_file(&path).unwrap());
+    } else if has_flag("--re24") {
+        options.run_expectancy = Some(Default::default());
+    }
+    if has_flag("--short-keys") {
+        options.key_names = model::SHORT_KEY_NAMES.iter().map(|(key, name)| (key.to_string(), name.to_string())).collect();
+    }
+    if let Some(path) = flag_value("--key-names") {
+        let key_names = serde_json::from_str::<std::collections::HashMap<String, String>>(&std::fs::read_to_string(&path).unwrap())
+            .expect("--key-names must be a JSON object of key names");
+        options.key_names.extend(key_names);
+    }
+    let play_types = |types: String| types.split(',').map(|t| t.trim().to_string()).collect::<Vec<String>>();
+    options.include_types = flag_value("--include-types").map(play_types);
+    options.exclude_types = flag_value("--exclude-types").map(play_types).unwrap_or_default();
+    let inning = |name: &str| flag_value(name).map(|inning| inning.parse().unwrap_or_else(|_| panic!("{name} must be an inning number")));
+    options.min_inning = inning("--min-inning");
+    options.max_inning = inning("--max-inning");
+    if has_flag("--anonymize") {
+        let seed = flag_value("--anonymize-seed").map_or(0, |seed| seed.parse().expect("--anonymize-seed must be a number"));
+        let global = match flag_value("--anonymize-scope").as_deref() {
+            None | Some("game") => false,
+            Some("global") => true,
+            Some(scope) => panic!("Unknown anonymize scope: {scope}"),
+        };
+        options.anonymize = Some(model::Anonymize::new(seed, global));
+    }
+
+    options
+}
+
+/// How many games to fetch at once, from the `--concurrency` flag.
+fn concurrency_from_args() -> usize {
+    flag_value("--concurrency").map_or(DEFAULT_CONCURRENCY, |concurrency| {
+        concurrency.parse().ok().filter(|concurrency| *concurrency > 0).expect("--concurrency must be a positive number")
+    })
+}
+
+/// How many games to preprocess at once, from the `--jobs` flag (the number of cores by default).
+fn jobs_from_args() -> usize {
+    match flag_value("--jobs") {
+        Some(jobs) => jobs.parse().ok().filter(|jobs| *jobs > 0).expect("--jobs must be a positive number"),
+        None => std::thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+    }
+}
+
+/// The format to save games in, from the `--format` flag (`json`, the default, or `msgpack`).
+fn game_format_from_args() -> storage::GameFormat {
+    match flag_value("--format").as_deref() {
+        None | Some("json") => storage::GameFormat::Json,
+        Some("msgpack") => storage::GameFormat::MessagePack,
+        Some(format) => panic!("Unknown game format: {format}"),
+    }
+}
+
+/// Whether to report progress as one JSON object per line on stderr, from the `--progress` flag
+/// (`bar`, the default, for progress bars, or `json`).
+fn json_progress_from_args() -> bool {
+    match flag_value("--progress").as_deref() {
+        None | Some("bar") => false,
+        Some("json") => true,
+        Some(progress) => panic!("Unknown progress format: {progress}"),
+    }
+}
+
+/// Whether to hide progress bars, from the `--quiet` flag.
+fn quiet_from_args() -> bool {
+    has_flag("--quiet")
+}
+
+/// The style of the progress bars, with the elapsed time and the time left.
+fn default_progress_style() -> ProgressStyle {
+    ProgressStyle::default_bar().template("{wide_bar} {pos}/{len} | elapsed: {elapsed_precise}, eta: {eta_precise}").unwrap()
+}
+
+/// A progress bar of the given length, or a hidden one if `--quiet` is given or progress is reported as JSON.
+/// If stderr is not a terminal (e.g. it is redirected to a log file), the bar is hidden and its progress is printed
+/// as a line of text every so often instead.
+fn progress_bar(length: u64, style: ProgressStyle) -> ProgressBar {
+    if quiet_from_args() || json_progress_from_args() {
+        return ProgressBar::hidden();
+    }
+    if std::io::stderr().is_terminal() {
+        return ProgressBar::new(length).with_style(style);
+    }
+
+    let progress = ProgressBar::hidden();
+    progress.set_length(length);
+    // the thread only holds a weak reference, so it stops once the bar is finished or dropped
+    let weak_progress = progress.downgrade();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(PROGRESS_LINE_INTERVAL);
+        let Some(progress) = weak_progress.upgrade().filter(|progress| !progress.is_finished()) else {
+            break;
+        };
+        eprintln!("{}", progress_line(&progress));
+    });
+
+    progress
+}
+
+/// A line of text describing the progress of `progress`, e.g. `120/2430 | elapsed: 00:05:00 | 147`.
+fn progress_line(progress: &ProgressBar) -> String {
+    let elapsed = progress.elapsed().as_secs();
+    let mut line = format!(
+        "{}/{} | elapsed: {:02}:{:02}:{:02}",
+        progress.position(),
+        progress.length().unwrap_or_default(),
+        elapsed / 3600,
+        elapsed / 60 % 60,
+        elapsed % 60,
+    );
+    let message = progress.message();
+    if !message.is_empty() {
+        line.push_str(&format!(" | {message}"));
+    }
+
+    line
+}
+
+/// Report a step in getting games as a JSON object on its own line of stderr
+/// (e.g. `{"event":"game_saved","game_pk":775296,"team":147}`), with the team whose games are being got, if any.
+fn emit_progress_event(event: &model::GameProgress, team_id: Option<u32>) {
+    let mut event = match event {
+        model::GameProgress::Scheduled { games } => serde_json::json!({ "event": "team_scheduled", "games": games }),
+        model::GameProgress::Started { game_pk } => serde_json::json!({ "event": "game_started", "game_pk": game_pk }),
+        model::GameProgress::Saved { game_pk } => serde_json::json!({ "event": "game_saved", "game_pk": game_pk }),
+        model::GameProgress::Skipped { game_pk } => serde_json::json!({ "event": "game_skipped", "game_pk": game_pk }),
+        model::GameProgress::Failed { game_pk, error } => {
+            serde_json::json!({ "event": "game_failed", "game_pk": game_pk, "error": error.to_string() })
+        },
+    };
+    if let Some(team_id) = team_id {
+        event["team"] = team_id.into();
+    }
+
+    eprintln!("{event}");
+}
+
+/// Report the end of a run as a JSON object on its own line of stderr, with its summary.
+fn emit_finished_event(summary: &model::FetchSummary) {
+    let mut event = serde_json::json!({ "event": "finished" });
+    event.as_object_mut().unwrap().extend(serde_json::to_value(summary).unwrap().as_object().unwrap().clone());
+
+    eprintln!("{event}");
+}
+
+/// The first season of the modern major leagues, the earliest that can be fetched by default.
+const MIN_SEASON: u16 = 1901;
+
+/// The current year (in UTC).
+fn current_year() -> u16 {
+    let days = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() / 86400;
+
+    // the civil calendar from days since the epoch, with years starting in March (Howard Hinnant's algorithm)
+    let days = days + 719468;
+    let (era, day_of_era) = (days / 146097, days % 146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153; // 0 is March
+    let year = era * 400 + year_of_era + if month >= 10 { 1 } else { 0 };
+
+    year as u16
+}
+
+/// The season given as the command's argument. Exits with an error if it is missing, not a year, or outside
+/// `--min-season` to `--max-season` (1901 to next year by default), rather than requesting a season that cannot exist.
+fn season_from_args() -> u16 {
+    let bound = |flag: &str, default: u16| flag_value(flag).map_or(default, |season| season.parse().unwrap_or_else(|_| panic!("{flag} must be a year")));
+    let (min_season, max_season) = (bound("--min-season", MIN_SEASON), bound("--max-season", current_year() + 1));
+
+    let exit = |message: String| -> ! {
+        eprintln!("{message}");
+        std::process::exit(1);
+    };
+    let Some(season) = std::env::args().nth(2) else {
+        exit("Please provide a season, e.g. 2024.".to_string());
+    };
+    match season.parse::<u32>() {
+        Ok(season) if (min_season as u32..=max_season as u32).contains(&season) => season as u16,
+        Ok(_) => exit(format!(
+            "Season {season} is out of range; seasons from {min_season} to {max_season} can be fetched (see --min-season and --max-season).",
+        )),
+        Err(_) => exit(format!("Invalid season: {season}; expected a year, e.g. 2024.")),
+    }
+}
+
+/// The sport to scrape, from the `--sport-id` flag (MLB by default).
+fn sport_id_from_args() -> u32 {
+    flag_value("--sport-id").map_or(storage::MLB_SPORT_ID, |sport_id| sport_id.parse().expect("--sport-id must be a number"))
+}
+
+/// The ids of the teams of the given sport that played in the given season, from the API,
+/// or the current MLB teams if they are not cached when offline.
+async fn team_ids_in_season(sport_id: u32, season: u16) -> Vec<u32> {
+    match model::Team::ids_in_season(sport_id, season).await {
+        Ok(team_ids) => team_ids,
+        Err(e) if has_flag("--offline") && sport_id == storage::MLB_SPORT_ID => {
+            tracing::warn!(season, error = %e, "Failed to get teams offline, using the current teams");
+            TEAM_IDS.to_vec()
+        },
+        Err(e) => panic!("Failed to get the teams in {season}: {e}"),
+    }
+}
+
+/// Fetch the schedule of the given season, and cache it in the data directory.
+async fn fetch_season_schedule(sport_id: u32, season: u16) -> Result<Vec<model::ScheduledGame>, String> {
+    let schedule = model::ScheduledGame::all_in_season(sport_id, season).await?;
+    storage::write_atomic(storage::schedule_path(sport_id, season), serde_json::to_string_pretty(&schedule).unwrap()).unwrap();
+
+    Ok(schedule)
+}
+
+/// The schedule of the given season. The cached schedule is used if none of its games can change any more,
+/// or if the schedule cannot be fetched; otherwise it is fetched (and cached) again.
+async fn season_schedule(sport_id: u32, season: u16) -> Vec<model::ScheduledGame> {
+    let cached = std::fs::read_to_string(storage::schedule_path(sport_id, season))
+        .ok()
+        .and_then(|schedule| serde_json::from_str::<Vec<model::ScheduledGame>>(&schedule).ok());
+    match cached {
+        Some(schedule) if schedule.iter().all(|game| game.is_final() || game.status.starts_with("Cancelled")) => schedule,
+        cached => match fetch_season_schedule(sport_id, season).await {
+            Ok(schedule) => schedule,
+            Err(e) => match cached {
+                Some(schedule) => {
+                    tracing::warn!(season, error = %e, "Failed to get the schedule, using the cached schedule");
+                    schedule
+                },
+                None => panic!("Failed to get the schedule of {season}: {e}"),
+            },
+        },
+    }
+}
+
+/// The key of a season's entry in `data/progress.json`. MLB seasons are keyed by the year alone,
+/// other sports by `{sport_id}/{year}`.
+fn progress_key(sport_id: u32, season: u16) -> String {
+    if sport_id == storage::MLB_SPORT_ID {
+        season.to_string()
+    } else {
+        format!("{sport_id}/{season}")
+    }
+}
+
+/// Where the games that were not final when they were fetched are kept, by season (keyed like `data/progress.json`),
+/// so they can be fetched again once they are.
+const NOT_FINAL_PATH: &str = "data/not_final.json";
+
+/// The games of the given season that were not final when they were fetched.
+fn load_not_final(sport_id: u32, season: u16) -> std::collections::BTreeSet<usize> {
+    let not_final = serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(NOT_FINAL_PATH).unwrap_or("{}".to_string())).unwrap();
+
+    not_final[progress_key(sport_id, season)]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|game_pk| game_pk.as_u64().map(|game_pk| game_pk as usize))
+        .collect()
+}
+
+/// Replace the games of the given season that were not final when they were fetched.
+fn save_not_final(sport_id: u32, season: u16, game_pks: &std::collections::BTreeSet<usize>) {
+    let mut not_final = serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(NOT_FINAL_PATH).unwrap_or("{}".to_string())).unwrap();
+
+    let key = progress_key(sport_id, season);
+    if game_pks.is_empty() {
+        not_final.as_object_mut().unwrap().remove(&key);
+    } else {
+        not_final[&key] = serde_json::json!(game_pks);
+    }
+
+    storage::write_atomic(NOT_FINAL_PATH, serde_json::to_string_pretty(&not_final).unwrap()).unwrap();
+}
+
+/// Remove the completed team from the list of teams (`team_ids`) to be processed in the given season.
+fn save_progress(sport_id: u32, season: u16, completed_team_id: u32, team_ids: &[u32]) {
     let mut progress = serde_json::from_str::<serde_json::Value>(std::fs::read_to_string("data/progress.json").unwrap_or("{}".to_string()).as_str()).unwrap();
 
-    if progress.get(&season.to_string()).is_none() {
-        progress[season.to_string()] = serde_json::Value::Array(TEAM_IDS.iter().map(|id| serde_json::Value::Number(serde_json::Number::from(*id))).collect());
+    let key = progress_key(sport_id, season);
+    if progress.get(&key).is_none() {
+        progress[&key] = serde_json::Value::Array(team_ids.iter().map(|id| serde_json::Value::Number(serde_json::Number::from(*id))).collect());
     }
 
-    let progress_season = progress.get_mut(&season.to_string()).unwrap().as_array_mut().unwrap();
+    let progress_season = progress.get_mut(&key).unwrap().as_array_mut().unwrap();
     progress_season.retain(|id| id.as_u64().unwrap() != completed_team_id as u64);
 
-    std::fs::write("data/progress.json", serde_json::to_string_pretty(&progress).unwrap()).unwrap();
+    storage::write_atomic("data/progress.json", serde_json::to_string_pretty(&progress).unwrap()).unwrap();
+}
+
+/// Replace the list of teams still to be processed in the given season with `team_ids`.
+fn rebuild_progress(sport_id: u32, season: u16, team_ids: &[u32]) {
+    let mut progress = std::fs::read_to_string("data/progress.json")
+        .ok()
+        .and_then(|progress| serde_json::from_str::<serde_json::Value>(&progress).ok())
+        .filter(serde_json::Value::is_object)
+        .unwrap_or(serde_json::json!({}));
+
+    progress[progress_key(sport_id, season)] = serde_json::json!(team_ids);
+    storage::write_atomic("data/progress.json", serde_json::to_string_pretty(&progress).unwrap()).unwrap();
+}
+
+/// The directory games are preprocessed into unless `--out-dir` is given.
+const DEFAULT_PREPROCESS_OUT_DIR: &str = "preprocessed_data";
+
+/// The directory to preprocess games into, from the `--out-dir` flag.
+fn preprocess_out_dir_from_args() -> std::path::PathBuf {
+    flag_value("--out-dir").unwrap_or(DEFAULT_PREPROCESS_OUT_DIR.to_string()).into()
+}
+
+/// The path the saved game at `game_path` is preprocessed to: the same place under `out_dir` as under `data`.
+fn preprocessed_path(game_path: &str, out_dir: &std::path::Path) -> std::path::PathBuf {
+    let game_path = std::path::Path::new(game_path);
+    storage::without_extensions(out_dir.join(game_path.strip_prefix("data").unwrap_or(game_path))).with_extension("jsonl")
+}
+
+/// The path of the manifest of the games preprocessed into `out_dir`, which maps the path of each saved game to the hash
+/// it had when it was last preprocessed.
+fn preprocess_manifest_path(out_dir: &std::path::Path) -> std::path::PathBuf {
+    out_dir.join("manifest.json")
 }
 
-/// Get all game pks for a given team in a given season.
-fn game_pks_for_team_in_season(team_id: u8, season: u16) -> Vec<usize> {
-    let all_games = glob(format!("data/{season}/**/*.json").as_str()).unwrap();
+/// The path of the marker of an unfinished preprocessing run into `out_dir`. Its first line is the arguments of the run
+/// (see [`preprocess_resume_arguments`]) and each line after that is the hash and path of a game it preprocessed, so that
+/// running again with the same arguments can carry on where it was interrupted. It is removed when the run finishes, and
+/// deleting it only means starting over.
+fn preprocess_resume_path(out_dir: &std::path::Path) -> std::path::PathBuf {
+    out_dir.join("resume.txt")
+}
+
+/// The arguments a preprocessing run is resumed with, which leave out those that do not change the output, so that e.g.
+/// an interrupted run can be carried on with `--force` or with another number of `--jobs`. Reordering them only means
+/// starting over.
+fn preprocess_resume_arguments() -> String {
+    preprocess_output_arguments(std::env::args().skip(2))
+}
+
+/// The given arguments of `preprocess`, without those that do not change the output, joined by spaces.
+fn preprocess_output_arguments(args: impl IntoIterator<Item = String>) -> String {
+    let mut args = args.into_iter();
+    let mut kept = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--force" | "--quiet" => {},
+            "--jobs" => {
+                args.next();
+            },
+            _ => kept.push(arg),
+        }
+    }
+
+    kept.join(" ")
+}
+
+/// The hash of each game an interrupted run with the given arguments preprocessed, by path, from its marker at `path`,
+/// or `None` if there is no marker or it is from a run with other arguments.
+fn read_preprocess_resume(path: &std::path::Path, arguments: &str) -> Option<std::collections::BTreeMap<String, String>> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut lines = contents.lines();
+    if lines.next() != Some(arguments) {
+        return None;
+    }
+
+    // a line cut short by the interruption has no space or a partial path, and at worst means preprocessing a game again
+    Some(lines.filter_map(|line| line.split_once(' ')).map(|(hash, game_path)| (game_path.to_string(), hash.to_string())).collect())
+}
+
+/// Open the marker at `path` to add to it, starting a new one for the given arguments unless an interrupted run is `resuming`.
+fn open_preprocess_resume(path: &std::path::Path, arguments: &str, resuming: bool) -> std::fs::File {
+    if resuming {
+        return std::fs::OpenOptions::new().append(true).open(path).unwrap();
+    }
+
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut file = std::fs::File::create(path).unwrap();
+    writeln!(file, "{arguments}").unwrap();
+
+    file
+}
+
+/// The hash identifying the saved game at `game_path`, the `arguments` it is preprocessed with (see
+/// [`preprocess_output_arguments`]) and the version of the preprocessing, which changes whenever any of them does.
+fn preprocess_hash(game_path: &str, arguments: &str) -> Option<String> {
+    let contents = std::fs::read(game_path).ok()?;
+    let version = model::PREPROCESS_VERSION.to_string();
+
+    Some(format!("{version}:{}", storage::content_hash([arguments.as_bytes(), b"\n", &contents].concat())))
+}
+
+/// Load the saved game at `game_path`, or log why it cannot be loaded (so that the caller can skip it).
+fn load_game(game_path: impl AsRef<std::path::Path>) -> Option<model::Game> {
+    model::Game::load(game_path).inspect_err(|e| tracing::warn!(error = %e, "Skipping unreadable game")).ok()
+}
+
+/// Preprocess the saved game at `game_path`, writing it to the same place under `out_dir`.
+/// Returns whether the game could be loaded.
+fn preprocess_game_file(game_path: &str, out_dir: &std::path::Path, options: &PreprocessOptions) -> bool {
+    let Some(game) = load_game(game_path) else {
+        return false;
+    };
+    let preprocessed = game.preprocess(options);
+    // let preprocessed = jsonxf::pretty_print(&preprocessed).unwrap();
+
+    let tokens_path = preprocessed_path(game_path, out_dir);
+    std::fs::create_dir_all(tokens_path.parent().unwrap()).unwrap();
+    std::fs::write(tokens_path, preprocessed).unwrap();
+
+    true
+}
+
+/// Call `f` with the index and path of each of the games in `game_paths`, on `jobs` threads, showing the progress.
+fn for_each_game_in_parallel(game_paths: &[String], jobs: usize, f: impl Fn(usize, &str) + Sync) {
+    let progress = progress_bar(game_paths.len() as u64, default_progress_style());
+    let next_game = std::sync::atomic::AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let index = next_game.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let Some(game_path) = game_paths.get(index) else {
+                        break;
+                    };
+                    f(index, game_path);
+                    progress.inc(1);
+                }
+            });
+        }
+    });
+    progress.finish();
+}
+
+/// Print how many regular season games of the given season are saved for each of its teams, flagging those with
+/// fewer than [`REPORT_THRESHOLD`] of the `expected` games. Each game is saved once, under its home team, but counts for both teams.
+fn print_team_report(sport_id: u32, season: u16, team_ids: &[u32], expected: usize) {
+    let game_paths = storage::glob_games(&format!("{}/{season}/*/*", storage::data_dir(sport_id)))
+        .iter()
+        .map(|game_path| game_path.to_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+
+    let games_per_team = std::sync::Mutex::new(team_ids.iter().map(|team_id| (*team_id, 0)).collect::<std::collections::BTreeMap<u32, usize>>());
+    let unreadable = std::sync::atomic::AtomicUsize::new(0);
+    for_each_game_in_parallel(&game_paths, jobs_from_args(), |_, game_path| {
+        let Some(game) = load_game(game_path) else {
+            unreadable.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            return;
+        };
+        let mut games_per_team = games_per_team.lock().unwrap();
+        for team in [&game.context.home_team, &game.context.away_team] {
+            *games_per_team.entry(team.id).or_default() += 1;
+        }
+    });
+
+    let games_per_team = games_per_team.into_inner().unwrap();
+    let mut flagged = 0;
+    println!("{:>6} {:>6}", "team", "games");
+    for (team_id, games) in &games_per_team {
+        if (*games as f64) < expected as f64 * REPORT_THRESHOLD {
+            flagged += 1;
+            println!("{team_id:>6} {games:>6}  <- {} fewer than {expected}", expected - games);
+        } else {
+            println!("{team_id:>6} {games:>6}");
+        }
+    }
+    let unreadable = unreadable.into_inner();
+    println!(
+        "{} games saved for {} teams ({unreadable} unreadable); {flagged} teams have fewer than {:.0}% of the {expected} games expected",
+        game_paths.len() - unreadable,
+        games_per_team.len(),
+        REPORT_THRESHOLD * 100.0,
+    );
+}
+
+/// Look up the players in `data/players.json` (see `players` and `warm-cache`) by id without requesting their names,
+/// before getting or parsing games.
+fn load_player_names() {
+    let Some(players) = std::fs::read_to_string("data/players.json")
+        .ok()
+        .and_then(|players| serde_json::from_str::<Vec<serde_json::Value>>(&players).ok())
+    else {
+        return;
+    };
+
+    model::cache_player_names(players.iter().filter_map(|player| {
+        Some((player["id"].as_u64()? as usize, player["name"].as_str()?.to_string()))
+    }));
+}
+
+/// Fetch the roster of every team in the season, and add their players to `data/players.json` (keeping those already
+/// in it), so that getting the season's games finds almost every player's name there instead of requesting it.
+async fn warm_player_names(sport_id: u32, season: u16) {
+    let team_ids = team_ids_in_season(sport_id, season).await;
+    let rosters = futures::stream::iter(team_ids.iter().copied())
+        .map(|team_id| async move { (team_id, model::Player::all_on_season_roster(team_id, season).await) })
+        .buffer_unordered(concurrency_from_args())
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut players = std::fs::read_to_string("data/players.json")
+        .ok()
+        .and_then(|players| serde_json::from_str::<Vec<serde_json::Value>>(&players).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|player| Some((player["id"].as_u64()? as usize, player)))
+        .collect::<std::collections::BTreeMap<_, _>>();
+    let (mut added, mut failed) = (0, 0);
+    for (team_id, roster) in rosters {
+        let roster = match roster {
+            Ok(roster) => roster,
+            Err(e) => {
+                tracing::warn!(team_id, error = %e, "Failed to get roster");
+                failed += 1;
+                continue;
+            },
+        };
+
+        for (id, name, position) in roster {
+            let player = players.entry(id).or_insert_with(|| {
+                added += 1;
+                serde_json::json!({ "id": id, "name": name, "positions": [] })
+            });
+            let positions = player["positions"].as_array_mut().unwrap();
+            if let Some(position) = position.map(|position| serde_json::Value::from(position.to_string())) {
+                if !positions.contains(&position) {
+                    positions.push(position);
+                }
+            }
+        }
+    }
+
+    println!("Added {added} players from the rosters of {} teams, {} players in total", team_ids.len() - failed, players.len());
+    if failed > 0 {
+        println!("{failed} rosters could not be fetched; run `warm-cache {season}` again to retry them");
+    }
+    let players = players.into_values().collect::<Vec<_>>();
+    storage::write_atomic("data/players.json", serde_json::to_string_pretty(&players).unwrap()).unwrap();
+}
+
+/// Get the paths of all saved games.
+fn all_game_paths() -> Vec<String> {
+    let ignore_paths = ["data/log.txt", "data/progress.json", "data/players.json", "data/not_final.json"];
+
+    storage::glob_games("data/**/*")
+        .iter()
+        .map(|game_path| game_path.to_str().unwrap().to_string())
+        .filter(|game_path| !ignore_paths.contains(&game_path.as_str()) && !game_path.starts_with("data/cache/"))
+        .collect::<Vec<String>>()
+}
+
+/// Write `data/players.json`, a list of every player in the saved games with their id, name and the positions they were seen at.
+fn write_player_manifest() {
+    let mut players = std::collections::BTreeMap::<usize, (String, std::collections::BTreeSet<String>)>::new();
+
+    let game_paths = all_game_paths();
+    for game_path in game_paths.iter().progress_with(progress_bar(game_paths.len() as u64, default_progress_style())) {
+        let Some(game) = load_game(game_path) else {
+            continue;
+        };
+
+        for player in game.context.home_team.players.iter().chain(game.context.away_team.players.iter()) {
+            if player.id == 0 {
+                continue; // saved before player ids were recorded
+            }
+
+            let (_, positions) = players.entry(player.id).or_insert_with(|| (player.name.clone(), Default::default()));
+            positions.insert(player.position.to_string());
+        }
+    }
+
+    let manifest = players
+        .into_iter()
+        .map(|(id, (name, positions))| serde_json::json!({ "id": id, "name": name, "positions": positions }))
+        .collect::<Vec<serde_json::Value>>();
+    println!("Found {} players", manifest.len());
+
+    storage::write_atomic("data/players.json", serde_json::to_string_pretty(&manifest).unwrap()).unwrap();
+}
+
+/// The paths of the games saved under `dir` whose file names (without the extension) match `game_pattern`,
+/// in the `{year}/{home_team_id}` layout or, outside the regular season, `{year}/{game_type}/{home_team_id}`.
+fn glob_saved_games(dir: &str, game_pattern: &str) -> Vec<std::path::PathBuf> {
+    let mut game_paths = storage::glob_games(&format!("{dir}/*/*/{game_pattern}"));
+    for game_type in model::GameType::ALL.iter().filter_map(model::GameType::dir_name) {
+        game_paths.extend(storage::glob_games(&format!("{dir}/*/{game_type}/*/{game_pattern}")));
+    }
+
+    game_paths
+}
+
+/// The game pks of the games saved under `dir`, in the `{year}/{home_team_id}/{game_pk}.{extension}` layout
+/// (or with a game type directory), mapped to their paths.
+fn saved_game_pks(dir: &str) -> std::collections::BTreeMap<usize, std::path::PathBuf> {
+    glob_saved_games(dir, "*")
+        .into_iter()
+        .filter_map(|game_path| Some((storage::game_pk_from_path(&game_path)?, game_path)))
+        .collect()
+}
+
+/// The path of the game with the given game pk, if it is saved under `data_dir`.
+fn saved_game_path(data_dir: &str, game_pk: usize) -> Option<std::path::PathBuf> {
+    glob_saved_games(data_dir, &game_pk.to_string()).into_iter().next()
+}
+
+/// Append every saved game that is not already in the file at `path` to it, as one JSON `Game` per line.
+fn export_ndjson(path: &str) {
+    use std::io::Seek;
+
+    // a line cut off by an interrupted export is dropped, and its game exported again
+    let existing = std::fs::read_to_string(path).unwrap_or_default();
+    let complete_len = existing.rfind('\n').map_or(0, |index| index + 1);
+    let exported_game_pks = existing[..complete_len]
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok()?["context"]["game_pk"].as_u64())
+        .map(|game_pk| game_pk as usize)
+        .collect::<std::collections::HashSet<usize>>();
+
+    let mut file = std::fs::OpenOptions::new().create(true).truncate(false).write(true).open(path).unwrap();
+    file.set_len(complete_len as u64).unwrap();
+    file.seek(std::io::SeekFrom::End(0)).unwrap();
+    let mut writer = std::io::BufWriter::new(file);
+
+    let mut added = 0;
+    let mut skipped = 0;
+    let game_paths = all_game_paths();
+    for game_path in game_paths.iter().progress_with(progress_bar(game_paths.len() as u64, default_progress_style())) {
+        let game_pk = storage::game_pk_from_path(game_path);
+        if game_pk.is_some_and(|game_pk| exported_game_pks.contains(&game_pk)) {
+            skipped += 1;
+            continue;
+        }
+
+        let Some(game) = load_game(game_path) else {
+            continue;
+        };
+        serde_json::to_writer(&mut writer, &game).unwrap();
+        writeln!(writer).unwrap();
+        added += 1;
+    }
+    writer.flush().unwrap();
+
+    println!("Exported {added} games, skipped {skipped} already in {path}");
+}
+
+/// Print the distribution of the number of tokens in each preprocessed game (counting whitespace-separated words),
+/// as a table or, with `json`, as a JSON object.
+fn print_token_stats(token_counts: &mut [usize], json: bool) {
+    if token_counts.is_empty() {
+        println!("No games");
+        return;
+    }
+
+    token_counts.sort_unstable();
+    let total = token_counts.iter().sum::<usize>();
+    // the nearest-rank percentile
+    let percentile = |p: usize| token_counts[(p * token_counts.len()).div_ceil(100).max(1) - 1];
+    let mean = total as f64 / token_counts.len() as f64;
+    let stats = [
+        ("games", serde_json::json!(token_counts.len())),
+        ("total", serde_json::json!(total)),
+        ("min", serde_json::json!(token_counts[0])),
+        ("mean", serde_json::json!((mean * 10.0).round() / 10.0)),
+        ("median", serde_json::json!(percentile(50))),
+        ("p90", serde_json::json!(percentile(90))),
+        ("p95", serde_json::json!(percentile(95))),
+        ("p99", serde_json::json!(percentile(99))),
+        ("max", serde_json::json!(token_counts[token_counts.len() - 1])),
+    ];
+
+    if json {
+        let stats = stats.into_iter().map(|(name, value)| (name.to_string(), value)).collect::<serde_json::Map<_, _>>();
+        println!("{}", serde_json::to_string_pretty(&stats).unwrap());
+    } else {
+        for (name, value) in stats {
+            println!("{name:<8} {:>12}", value.to_string());
+        }
+    }
+}
+
+/// Bring every saved game with an older `schema_version` up to the current schema, in the same place and format (see
+/// [`model::Migration`]). Games that have to be parsed again are left for `reparse` if their raw responses are saved,
+/// and listed in `data/refetch.txt` along with games that no longer load otherwise, so they can be fetched again with `getmany`.
+fn migrate_games() {
+    let (mut migrated, mut current, mut reparse) = (0, 0, 0);
+    let mut refetch = Vec::new();
+    let game_paths = all_game_paths();
+    for game_path in game_paths.iter().progress_with(progress_bar(game_paths.len() as u64, default_progress_style())) {
+        let mut game = match model::Game::load(game_path) {
+            Ok(game) => game,
+            Err(e) => {
+                tracing::warn!(path = %game_path, error = %e, "Game cannot be migrated");
+                if let Some(game_pk) = storage::game_pk_from_path(game_path) {
+                    refetch.push(game_pk.to_string());
+                }
+                continue;
+            },
+        };
+        match game.migration() {
+            None => current += 1,
+            Some(model::Migration::Rewrite) => {
+                game.schema_version = model::SCHEMA_VERSION;
+                game.write(game_path, storage::GameFormat::from_path(game_path).unwrap()).unwrap();
+                migrated += 1;
+            },
+            Some(model::Migration::Reparse) if storage::raw_response_path(game_path, storage::RawResponse::Feed).exists() => reparse += 1,
+            Some(model::Migration::Reparse) => {
+                tracing::warn!(path = %game_path, schema_version = game.schema_version, "Game must be fetched again to be migrated");
+                if let Some(game_pk) = storage::game_pk_from_path(game_path) {
+                    refetch.push(game_pk.to_string());
+                }
+            },
+        }
+    }
+
+    println!("Migrated {migrated} games, {current} already current");
+    if reparse > 0 {
+        println!("{reparse} games must be parsed again; run `reparse` to parse them from their saved responses");
+    }
+    if !refetch.is_empty() {
+        storage::write_atomic("data/refetch.txt", refetch.join("\n") + "\n").unwrap();
+        println!("{} games could not be migrated; run `getmany data/refetch.txt --force` to fetch them again", refetch.len());
+    }
+}
+
+/// Parse every saved game that has its raw responses saved next to it (see `--save-raw`) again, with the current
+/// parser and the given options, and rewrite it in the same place and format. Nothing is fetched.
+async fn reparse_games(options: &model::ParseOptions) {
+    load_player_names();
+    let (mut reparsed, mut without_responses, mut failed) = (0, 0, 0);
+    let game_paths = all_game_paths();
+    for game_path in game_paths.iter().progress_with(progress_bar(game_paths.len() as u64, default_progress_style())) {
+        let read_response = |response| {
+            let path = storage::raw_response_path(game_path, response);
+            std::fs::read_to_string(path).ok().and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        };
+        let Some(game_data) = read_response(storage::RawResponse::Feed) else {
+            without_responses += 1;
+            continue;
+        };
+        // a game saved with --optional-boxscore may have no boxscore
+        let boxscore_data = read_response(storage::RawResponse::Boxscore).unwrap_or_default();
+
+        match model::Game::from_feed_and_boxscore(&game_data, &boxscore_data, options).await {
+            Ok(game) => {
+                game.write(game_path, storage::GameFormat::from_path(game_path).unwrap()).unwrap();
+                reparsed += 1;
+            },
+            Err(e) => {
+                tracing::warn!(path = %game_path, error = %e, "Failed to parse game again");
+                failed += 1;
+            },
+        }
+    }
+
+    println!("Parsed {reparsed} games again, skipped {without_responses} without saved responses and {failed} that failed to parse");
+}
+
+/// Find games saved more than once (under different paths, or in different formats) and keep only the most
+/// recently modified copy of each, or the oldest with `keep_oldest`. Nothing is deleted unless `apply` is set.
+fn dedup_games(apply: bool, keep_oldest: bool) {
+    let mut games = std::collections::BTreeMap::<usize, Vec<std::path::PathBuf>>::new();
+    for game_path in all_game_paths() {
+        let game_path = std::path::PathBuf::from(game_path);
+        if let Some(game_pk) = storage::game_pk_from_path(&game_path) {
+            games.entry(game_pk).or_default().push(game_path);
+        }
+    }
+
+    let mut duplicates = 0;
+    for (game_pk, mut game_paths) in games.into_iter().filter(|(_, game_paths)| game_paths.len() > 1) {
+        game_paths.sort_by_key(|game_path| std::fs::metadata(game_path).and_then(|metadata| metadata.modified()).ok());
+        let kept = if keep_oldest { game_paths.remove(0) } else { game_paths.pop().unwrap() };
+
+        println!("Game {game_pk}: keeping {}", kept.display());
+        for game_path in game_paths {
+            if apply {
+                std::fs::remove_file(&game_path).unwrap();
+                println!("  removed {}", game_path.display());
+            } else {
+                println!("  would remove {}", game_path.display());
+            }
+            duplicates += 1;
+        }
+    }
+
+    if apply {
+        println!("Removed {duplicates} duplicate games");
+    } else {
+        println!("Found {duplicates} duplicate games; pass --apply to remove them");
+    }
+}
+
+/// Copy the games saved under `src` into `dst`, skipping those already in `dst` and any that cannot be read.
+fn merge_datasets(src: &str, dst: &str) {
+    let dst_game_pks = saved_game_pks(dst);
+
+    let mut added = 0;
+    let mut skipped = 0;
+    let mut invalid = 0;
+    let src_game_pks = saved_game_pks(src);
+    let progress = progress_bar(src_game_pks.len() as u64, default_progress_style());
+    for (game_pk, game_path) in src_game_pks.into_iter().progress_with(progress) {
+        if dst_game_pks.contains_key(&game_pk) {
+            skipped += 1;
+            continue;
+        }
+
+        // a partially-copied file will not parse, so it is not merged
+        if let Err(e) = model::Game::load(&game_path) {
+            tracing::warn!(path = %game_path.display(), error = %e, "Skipping unreadable game");
+            invalid += 1;
+            continue;
+        }
+
+        let relative_path = game_path.strip_prefix(src).unwrap();
+        storage::write_atomic(std::path::Path::new(dst).join(relative_path), std::fs::read(&game_path).unwrap()).unwrap();
+        added += 1;
+    }
+
+    println!("Added {added} games, skipped {skipped} already present and {invalid} unreadable");
+}
+
+/// Lock the data directory `dir` for a command that writes to it, exiting if another command is already writing to it.
+fn lock_data_dir(dir: &str) -> std::fs::File {
+    storage::lock_dir(dir).unwrap_or_else(|e| {
+        eprintln!("{e}; wait for it to finish before running this command.");
+        std::process::exit(1);
+    })
+}
+
+/// Get all game pks for a given team of the given sport in a given season.
+fn game_pks_for_team_in_season(sport_id: u32, team_id: u32, season: u16) -> Vec<usize> {
+    let all_games = storage::glob_games(&format!("{}/{season}/**/*", storage::data_dir(sport_id)));
 
     let mut game_pks = Vec::new();
+    // a game that cannot be loaded is not counted, so it is fetched again
     for game_path in all_games {
-        let game_path = game_path.unwrap();
-        let game = serde_json::from_str::<model::Game>(&std::fs::read_to_string(game_path).unwrap()).unwrap();
+        let Some(game) = load_game(game_path) else {
+            continue;
+        };
 
         if game.context.home_team.id == team_id || game.context.away_team.id == team_id {
             game_pks.push(game.context.game_pk);
@@ -39,70 +963,388 @@ fn game_pks_for_team_in_season(team_id: u8, season: u16) -> Vec<usize> {
     game_pks
 }
 
+/// Get and save the games with the given game pks (of the sport given by `--sport-id`), skipping those that are
+/// already saved unless `--force` is passed, and print a summary.
+async fn get_many(game_pks: Vec<usize>) {
+    load_player_names();
+    let data_dir = storage::data_dir(sport_id_from_args());
+    let saved_game_pks = if has_flag("--force") { Default::default() } else { saved_game_pks(&data_dir) };
+    let parse_options = parse_options_from_args();
+    let json_progress = json_progress_from_args();
+    let mut summary = model::FetchSummary::default();
+
+    let progress = progress_bar(game_pks.len() as u64, default_progress_style());
+    let (skipped_game_pks, game_pks) = game_pks.into_iter().partition::<Vec<usize>, _>(|game_pk| saved_game_pks.contains_key(game_pk));
+    for game_pk in skipped_game_pks {
+        tracing::info!(game_pk, "Skipping game");
+        summary.skipped_existing += 1;
+        if json_progress {
+            emit_progress_event(&model::GameProgress::Skipped { game_pk }, None);
+        }
+        progress.inc(1);
+    }
+
+    let (data_dir, parse_options) = (&data_dir, &parse_options);
+    let mut results = futures::stream::iter(game_pks)
+        .map(|game_pk| async move {
+            (game_pk, model::Game::get_and_save(game_pk, data_dir, game_format_from_args(), parse_options).await)
+        })
+        .buffer_unordered(concurrency_from_args());
+    while let Some((game_pk, result)) = results.next().await {
+        summary.record(&result);
+        if json_progress {
+            emit_progress_event(&model::GameProgress::from_result(game_pk, result), None);
+        }
+        progress.inc(1);
+    }
+    progress.finish();
+    if json_progress {
+        emit_finished_event(&summary);
+    }
+    println!("{summary}");
+}
+
 #[tokio::main]
 async fn main() {
+    init_logging();
+    api::configure(api_config_from_args());
+
     match std::env::args().nth(1) {
         Some(command) => match command.as_str() {
             "get" => {
-                let season = std::env::args().nth(2).unwrap().parse::<u16>().unwrap();
+                let _lock = lock_data_dir("data");
+                let season = season_from_args();
                 let progress = serde_json::from_str::<serde_json::Value>(std::fs::read_to_string("data/progress.json").unwrap_or("{}".to_string()).as_str()).unwrap();
-                let progress_season = match progress.get(&season.to_string()) {
-                    Some(progress_season) => progress_season.as_array().unwrap().iter().map(|id| id.as_u64().unwrap() as u8).collect(),
-                    None => TEAM_IDS.to_vec(),
+                let sport_id = sport_id_from_args();
+                let team_ids = team_ids_in_season(sport_id, season).await;
+                let progress_season = match progress.get(progress_key(sport_id, season)) {
+                    Some(progress_season) => progress_season.as_array().unwrap().iter().map(|id| id.as_u64().unwrap() as u32).collect(),
+                    None => team_ids.clone(),
                 };
                 println!("Processing season {} for {} teams ({:?})", season, progress_season.len(), progress_season);
+                load_player_names();
+
+                let parse_options = parse_options_from_args();
+                let force = has_flag("--force");
+                let concurrency = concurrency_from_args();
+                let format = game_format_from_args();
+                let json_progress = json_progress_from_args();
+                let mut summary = model::FetchSummary::default();
+                let data_dir = storage::data_dir(sport_id);
 
-                let progress_style = ProgressStyle::default_bar().template("{wide_bar} {pos}/{len} | elapsed: {elapsed_precise}, eta: {eta_precise}").unwrap();
-                for team_id in progress_season.iter().progress_with_style(progress_style) {
-                    let _ = model::Game::get_all_by_team_in_season(
+                // games of teams that are already done are not fetched again by the teams below
+                let mut not_final = load_not_final(sport_id, season);
+                // the games got so far in this run, which are not got again for another team
+                let mut handled_game_pks = not_final.clone();
+                if !not_final.is_empty() {
+                    println!("Retrying {} games that were not final", not_final.len());
+                    let (data_dir, parse_options) = (&data_dir, &parse_options);
+                    let mut results = futures::stream::iter(not_final.clone())
+                        .map(|game_pk| async move { (game_pk, model::Game::get_and_save(game_pk, data_dir, format, parse_options).await) })
+                        .buffer_unordered(concurrency);
+                    while let Some((game_pk, result)) = results.next().await {
+                        if !matches!(result, Err(model::GameError::NotFinal)) {
+                            not_final.remove(&game_pk);
+                        }
+                        summary.record(&result);
+                        if json_progress {
+                            emit_progress_event(&model::GameProgress::from_result(game_pk, result), None);
+                        }
+                    }
+                    save_not_final(sport_id, season, &not_final);
+                }
+
+                // the whole season's schedule is fetched once, and each game is only got for one of its teams
+                let schedule = season_schedule(sport_id, season).await;
+                let team_game_pks = model::ScheduledGame::by_team(&schedule, &progress_season);
+
+                let team_progress_style = ProgressStyle::default_bar().template("{wide_bar} {pos}/{len} | {msg}").unwrap();
+                let season_progress = progress_bar(progress_season.len() as u64, default_progress_style());
+                for (team_id, game_pks) in progress_season.iter().zip(team_game_pks).progress_with(season_progress) {
+                    let team_progress = progress_bar(0, team_progress_style.clone());
+                    let game_pks = game_pks.into_iter().filter(|game_pk| handled_game_pks.insert(*game_pk)).collect();
+                    summary += model::Game::get_all_by_team_in_season(
+                        sport_id,
                         *team_id,
-                        season,
-                        game_pks_for_team_in_season(*team_id, season),
+                        game_pks,
+                        if force { Vec::new() } else { game_pks_for_team_in_season(sport_id, *team_id, season) },
+                        format,
+                        &parse_options,
+                        concurrency,
+                        |event| {
+                            if json_progress {
+                                emit_progress_event(&event, Some(*team_id));
+                            }
+                            match event {
+                                model::GameProgress::Scheduled { games } => team_progress.set_length(games as u64),
+                                model::GameProgress::Started { game_pk } => team_progress.set_message(format!("fetching {game_pk}")),
+                                model::GameProgress::Saved { game_pk } => {
+                                    team_progress.set_message(format!("saved {game_pk}"));
+                                    team_progress.inc(1);
+                                },
+                                model::GameProgress::Skipped { game_pk } => {
+                                    team_progress.set_message(format!("skipped {game_pk}"));
+                                    team_progress.inc(1);
+                                },
+                                model::GameProgress::Failed { game_pk, error } => {
+                                    if matches!(error, model::GameError::NotFinal) {
+                                        not_final.insert(game_pk);
+                                    }
+                                    team_progress.set_message(format!("failed {game_pk}: {error}"));
+                                    team_progress.inc(1);
+                                },
+                            }
+                        },
                     ).await;
-                    save_progress(season, *team_id);
+                    team_progress.finish_and_clear();
+                    save_not_final(sport_id, season, &not_final);
+                    save_progress(sport_id, season, *team_id, &team_ids);
+                }
+                if json_progress {
+                    emit_finished_event(&summary);
                 }
+                println!("{summary}");
+            },
+            "schedule" => {
+                let _lock = lock_data_dir("data");
+                let season = season_from_args();
+                let sport_id = sport_id_from_args();
+
+                let schedule = fetch_season_schedule(sport_id, season).await.unwrap_or_else(|e| panic!("Failed to get the schedule of {season}: {e}"));
+                println!(
+                    "Saved the schedule of {season} ({} games, {} final) to {}",
+                    schedule.len(),
+                    schedule.iter().filter(|game| game.is_final()).count(),
+                    storage::schedule_path(sport_id, season).display(),
+                );
+            },
+            "missing" => {
+                let _lock = has_flag("--retry").then(|| lock_data_dir("data"));
+                let season = season_from_args();
+                let sport_id = sport_id_from_args();
+
+                let schedule = season_schedule(sport_id, season).await;
+                let saved_game_pks = saved_game_pks(&storage::data_dir(sport_id));
+                let missing = model::ScheduledGame::missing(&schedule, |game_pk| saved_game_pks.contains_key(&game_pk));
+                // the game pks go to stdout alone, so they can be piped into getmany
+                for game_pk in &missing {
+                    println!("{game_pk}");
+                }
+                eprintln!(
+                    "{} of the {} final games of {season} are missing",
+                    missing.len(),
+                    schedule.iter().filter(|game| game.is_final()).count(),
+                );
+
+                if has_flag("--retry") && !missing.is_empty() {
+                    get_many(missing).await;
+                }
+            },
+            "report" => {
+                let season = season_from_args();
+                let sport_id = sport_id_from_args();
+                let expected = flag_value("--expected").map_or(DEFAULT_EXPECTED_GAMES, |expected| {
+                    expected.parse().expect("--expected must be a number")
+                });
+
+                let team_ids = team_ids_in_season(sport_id, season).await;
+                print_team_report(sport_id, season, &team_ids, expected);
+            },
+            "warm-cache" => {
+                let _lock = lock_data_dir("data");
+                warm_player_names(sport_id_from_args(), season_from_args()).await;
+            },
+            "reindex" => {
+                let _lock = lock_data_dir("data");
+                let season = season_from_args();
+                let sport_id = sport_id_from_args();
+
+                // a team is done once every final game it played is saved, whichever of the two teams it was got for
+                let team_ids = team_ids_in_season(sport_id, season).await;
+                let schedule = season_schedule(sport_id, season).await;
+                let saved_game_pks = saved_game_pks(&storage::data_dir(sport_id));
+                let remaining = model::ScheduledGame::teams_missing_games(&schedule, &team_ids, |game_pk| saved_game_pks.contains_key(&game_pk));
+                rebuild_progress(sport_id, season, &remaining);
+                println!(
+                    "Rebuilt the progress of {season}: {} of {} teams are done, {} still to process ({remaining:?})",
+                    team_ids.len() - remaining.len(),
+                    team_ids.len(),
+                    remaining.len(),
+                );
             },
             "preprocess" => {
-                let ignore_paths = ["data/log.txt", "data/progress.json"];
-                let all_games = glob("data/**/*.json")
-                    .unwrap()
-                    .collect::<Vec<_>>()
-                    .iter()
-                    .map(|game_path| game_path.as_ref().unwrap().to_str().unwrap().to_string())
-                    .filter(|game_path| !ignore_paths.contains(&game_path.as_str()))
-                    .collect::<Vec<String>>();
-
-                let progress_style = ProgressStyle::default_bar().template("{wide_bar} {pos}/{len} | elapsed: {elapsed_precise}, eta: {eta_precise}").unwrap();
-                for game_path in all_games.iter().progress_with_style(progress_style) {
-                    let game = serde_json::from_str::<model::Game>(&std::fs::read_to_string(game_path).unwrap()).unwrap();
-                    let preprocessed = game.preprocess();
-                    // let preprocessed = jsonxf::pretty_print(&preprocessed).unwrap();
-
-                    let tokens_path = game_path
-                        .replace("data", "preprocessed_data")
-                        .replace(".json", ".jsonl");
-
-                    let parts = tokens_path
-                        .split('/')
-                        .rev()
-                        .skip(1)
-                        .collect::<Vec<&str>>()
-                        .iter()
-                        .rev()
-                        .map(|part| part.to_string())
-                        .collect::<Vec<String>>()
-                        .join("/");
-
-                    std::fs::create_dir_all(parts).unwrap();
-                    std::fs::write(tokens_path, preprocessed).unwrap();
+                let all_games = all_game_paths();
+                let options = preprocess_options_from_args();
+                // global pseudonyms are numbered in the order games are preprocessed, so that must not vary
+                let global_anonymize = options.anonymize.as_ref().is_some_and(model::Anonymize::is_global);
+                let jobs = if global_anonymize { 1 } else { jobs_from_args() };
+                // skipping games would also change the global pseudonyms of the games after them
+                let force = has_flag("--force") || global_anonymize;
+                if let Some(output) = flag_value("--output") {
+                    let output_file = storage::SharedWriter::create(&output, has_flag("--sorted")).unwrap();
+                    for_each_game_in_parallel(&all_games, jobs, |index, game_path| {
+                        // an unreadable game still takes its place in the order, empty
+                        let preprocessed = load_game(game_path).map(|game| game.preprocess(&options)).unwrap_or_default();
+                        output_file.write(index, preprocessed).unwrap();
+                    });
+                    output_file.finish().unwrap();
+                    println!("Preprocessed {} games into {output}", all_games.len());
+                    return;
+                }
+
+                let skipped = std::sync::atomic::AtomicUsize::new(0);
+                let unreadable = std::sync::atomic::AtomicUsize::new(0);
+                let out_dir = preprocess_out_dir_from_args();
+                let manifest_path = preprocess_manifest_path(&out_dir);
+                let manifest = std::fs::read_to_string(&manifest_path)
+                    .ok()
+                    .and_then(|manifest| serde_json::from_str::<std::collections::BTreeMap<String, String>>(&manifest).ok())
+                    .unwrap_or_default();
+                // the games an interrupted run preprocessed are not preprocessed again, even with --force (but the marker is
+                // not used with global pseudonyms, whose numbering depends on preprocessing every game in one run)
+                let resume_path = preprocess_resume_path(&out_dir);
+                let resume_arguments = preprocess_resume_arguments();
+                let resumed = read_preprocess_resume(&resume_path, &resume_arguments).filter(|_| !global_anonymize);
+                let resume_file = (!global_anonymize)
+                    .then(|| std::sync::Mutex::new(open_preprocess_resume(&resume_path, &resume_arguments, resumed.is_some())));
+                let resumed = resumed.unwrap_or_default();
+                let hashes = std::sync::Mutex::new(std::collections::BTreeMap::new());
+                for_each_game_in_parallel(&all_games, jobs, |_, game_path| {
+                    let Some(hash) = preprocess_hash(game_path, &resume_arguments) else {
+                        tracing::warn!(path = %game_path, "Skipping unreadable game");
+                        unreadable.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        return;
+                    };
+                    let done = resumed.get(game_path) == Some(&hash) || !force && manifest.get(game_path) == Some(&hash);
+                    if done && preprocessed_path(game_path, &out_dir).exists() {
+                        skipped.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    } else if !preprocess_game_file(game_path, &out_dir, &options) {
+                        // not recorded, so it is tried again on the next run
+                        unreadable.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        return;
+                    }
+                    if let Some(resume_file) = &resume_file {
+                        writeln!(resume_file.lock().unwrap(), "{hash} {game_path}").unwrap();
+                    }
+                    hashes.lock().unwrap().insert(game_path.to_string(), hash);
+                });
+                let hashes = serde_json::to_string_pretty(&hashes.into_inner().unwrap()).unwrap();
+                storage::write_atomic(&manifest_path, hashes).unwrap();
+                if resume_file.is_some() {
+                    std::fs::remove_file(&resume_path).unwrap();
                 }
+                let (skipped, unreadable) = (skipped.into_inner(), unreadable.into_inner());
+                println!(
+                    "Preprocessed {} games, skipped {skipped} already preprocessed and {unreadable} unreadable",
+                    all_games.len() - skipped - unreadable,
+                );
+            },
+            "token-stats" => {
+                let all_games = all_game_paths();
+                let options = preprocess_options_from_args();
+                let jobs = if options.anonymize.as_ref().is_some_and(model::Anonymize::is_global) { 1 } else { jobs_from_args() };
+
+                let token_counts = std::sync::Mutex::new(Vec::with_capacity(all_games.len()));
+                for_each_game_in_parallel(&all_games, jobs, |_, game_path| {
+                    if let Some(game) = load_game(game_path) {
+                        token_counts.lock().unwrap().push(game.preprocess(&options).split_whitespace().count());
+                    }
+                });
+                print_token_stats(&mut token_counts.into_inner().unwrap(), has_flag("--json"));
+            },
+            "players" => write_player_manifest(),
+            "export-ndjson" => {
+                let path = std::env::args().nth(2).expect("Please provide a file to export the games to.");
+                export_ndjson(&path);
+            },
+            "migrate" => {
+                let _lock = lock_data_dir("data");
+                migrate_games();
+            },
+            "reparse" => {
+                let _lock = lock_data_dir("data");
+                reparse_games(&parse_options_from_args()).await;
+            },
+            "dedup" => {
+                let keep_oldest = match flag_value("--keep").as_deref() {
+                    None | Some("newest") => false,
+                    Some("oldest") => true,
+                    Some(keep) => panic!("Unknown --keep value: {keep}"),
+                };
+                let _lock = has_flag("--apply").then(|| lock_data_dir("data"));
+                dedup_games(has_flag("--apply"), keep_oldest);
+            },
+            "merge" => {
+                let src = std::env::args().nth(2).expect("Please provide a source data directory.");
+                let dst = std::env::args().nth(3).expect("Please provide a destination data directory.");
+                let _lock = lock_data_dir(&dst);
+                merge_datasets(&src, &dst);
+            },
+            "getmany" => {
+                let _lock = lock_data_dir("data");
+                let source = std::env::args().nth(2).expect("Please provide a file of game pks, or - to read them from stdin.");
+                let game_pks = if source == "-" {
+                    std::io::read_to_string(std::io::stdin()).unwrap()
+                } else {
+                    std::fs::read_to_string(&source).unwrap()
+                };
+                let game_pks = game_pks
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(|line| line.parse::<usize>().unwrap_or_else(|_| panic!("Invalid game pk: {line}")))
+                    .collect::<Vec<usize>>();
+                // a game listed more than once is only got once
+                let mut listed_game_pks = std::collections::HashSet::new();
+                let game_pks = game_pks.into_iter().filter(|game_pk| listed_game_pks.insert(*game_pk)).collect::<Vec<usize>>();
+
+                get_many(game_pks).await;
             },
             "getone" => {
+                let _lock = lock_data_dir("data");
+                let game_pk = std::env::args().nth(2).unwrap().parse::<usize>().unwrap();
+                let data_dir = storage::data_dir(sport_id_from_args());
+                if let Some(game_path) = saved_game_path(&data_dir, game_pk) {
+                    if !has_flag("--force") {
+                        println!("Game {game_pk} is already saved at {}; pass --force to fetch it again", game_path.display());
+                        return;
+                    }
+                }
+
+                load_player_names();
+                model::Game::get_and_save(game_pk, &data_dir, game_format_from_args(), &parse_options_from_args()).await.unwrap();
+            },
+            "show" => {
                 let game_pk = std::env::args().nth(2).unwrap().parse::<usize>().unwrap();
-                let _ = model::Game::from_game_pk(game_pk).await.unwrap();
+                let game = match saved_game_path(&storage::data_dir(sport_id_from_args()), game_pk) {
+                    Some(game_path) => model::Game::load(game_path).unwrap(),
+                    None => {
+                        load_player_names();
+                        model::Game::from_game_pk(game_pk, &parse_options_from_args()).await.unwrap()
+                    },
+                };
+
+                print!("{}", game.preprocess(&preprocess_options_from_args()));
             },
             _ => eprintln!("Unknown command."),
         },
         None => eprintln!("Please provide a command."),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changing_an_option_preprocesses_again() {
+        let game_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/games/unversioned.json");
+        let hash = |args: &[&str]| preprocess_hash(game_path, &preprocess_output_arguments(args.iter().map(|arg| arg.to_string()))).unwrap();
+
+        assert_ne!(hash(&[]), hash(&["--compact"]));
+        assert_ne!(hash(&["--output-format", "json"]), hash(&["--output-format", "text"]));
+        assert_ne!(hash(&["--min-inning", "3"]), hash(&["--min-inning", "4"]));
+        // but not one that leaves the output as it is
+        assert_eq!(hash(&["--descriptions"]), hash(&["--descriptions", "--force", "--jobs", "4", "--quiet"]));
+    }
+}
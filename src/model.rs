@@ -1,77 +1,278 @@
-use indicatif::{ProgressIterator, ProgressStyle};
+use futures::StreamExt;
 use serde::{Serialize, Deserialize};
-use std::io::Write;
 
-pub trait Preprocess {
-    /// Returns a JSON string representing the object.
-    fn preprocess(&self) -> String;
+use crate::storage::GameFormat;
+
+/// Replaces player names with pseudonyms (`PLAYER_1`, `PLAYER_2`, ...), consistently within a game,
+/// or across every game preprocessed with the same `Anonymize` if it is global.
+#[derive(Debug, Clone)]
+pub struct Anonymize {
+    seed: u64,
+    global: bool,
+    pseudonyms: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, usize>>>,
+}
+
+impl Anonymize {
+    pub fn new(seed: u64, global: bool) -> Self {
+        Self {
+            seed,
+            global,
+            pseudonyms: Default::default(),
+        }
+    }
+
+    /// Whether pseudonyms are shared across games, so games must be anonymized in a fixed order.
+    pub fn is_global(&self) -> bool {
+        self.global
+    }
+
+    /// A hash of the name that only depends on the seed (FNV-1a, seeded through the offset basis).
+    fn hash(&self, name: &str) -> u64 {
+        name.bytes().fold(0xcbf29ce484222325 ^ self.seed, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+    }
+
+    /// Assign pseudonyms to the given players, in an order determined by the seed.
+    fn pseudonyms(&self, names: Vec<String>) -> std::collections::HashMap<String, String> {
+        let mut names = names;
+        names.sort_by_key(|name| (self.hash(name), name.clone()));
+        names.dedup();
+
+        let mut local_pseudonyms = std::collections::HashMap::new();
+        let mut pseudonyms = self.pseudonyms.lock().unwrap();
+        let pseudonyms = if self.global { &mut *pseudonyms } else { &mut local_pseudonyms };
+
+        names
+            .into_iter()
+            .map(|name| {
+                let next_number = pseudonyms.len() + 1;
+                let number = *pseudonyms.entry(name.clone()).or_insert(next_number);
+
+                (name, format!("PLAYER_{number}"))
+            })
+            .collect()
+    }
+}
+
+/// Shorter names for the keys of the preprocessed output (mostly those of the play lines), so it takes fewer tokens.
+pub const SHORT_KEY_NAMES: [(&str, &str); 27] = [
+    ("index", "i"),
+    ("inning", "inn"),
+    ("type", "t"),
+    ("number", "n"),
+    ("description", "desc"),
+    ("batter", "bat"),
+    ("pitcher", "pit"),
+    ("catcher", "c"),
+    ("fielders", "f"),
+    ("fielder", "f"),
+    ("runner", "r"),
+    ("scoring_runner", "sr"),
+    ("base", "b"),
+    ("hit_data", "hd"),
+    ("exit_velocity", "ev"),
+    ("launch_angle", "la"),
+    ("total_distance", "dist"),
+    ("movements", "m"),
+    ("start_base", "s"),
+    ("end_base", "e"),
+    ("is_out", "out"),
+    ("pitches", "p"),
+    ("call", "cl"),
+    ("pitch_type", "pt"),
+    ("at_bat", "ab"),
+    ("pitch_count", "pc"),
+    ("total_bases", "tb"),
+];
+
+/// How the fielders credited in a play are emitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FielderFormat {
+    /// Their names (or whatever stands in for names, e.g. ids).
+    #[default]
+    Names,
+    /// The position numbers they were credited at (1 for the pitcher to 9 for the right fielder).
+    Positions,
+    /// Objects with both.
+    Both,
+}
+
+/// Options controlling how objects are preprocessed.
+#[derive(Debug, Clone, Default)]
+pub struct PreprocessOptions {
+    /// Emit player ids instead of player names.
+    pub player_ids: bool,
+    /// Emit each play's official description on its introduction line.
+    pub descriptions: bool,
+    /// Emit pseudonyms instead of player names. Takes precedence over `player_ids`.
+    pub anonymize: Option<Anonymize>,
+    /// Annotate each play that changes the base-out state with its change in run expectancy (RE24).
+    pub run_expectancy: Option<crate::run_expectancy::RunExpectancy>,
+    /// Only emit plays of these types, if given.
+    pub include_types: Option<Vec<String>>,
+    /// Never emit plays of these types.
+    pub exclude_types: Vec<String>,
+    /// Only emit plays in this inning or later, if given.
+    pub min_inning: Option<u8>,
+    /// Only emit plays in this inning or earlier, if given.
+    pub max_inning: Option<u8>,
+    /// Emit everything in lowercase.
+    pub lowercase: bool,
+    /// Emit the pitches of each plate appearance on a line after the play's introduction line.
+    pub pitches: bool,
+    /// Annotate each batted ball's introduction line with its outcome (see [`Play::outcome`]).
+    pub outcomes: bool,
+    /// Emit a line with the at-bat index and number of pitches before the first play of each plate appearance.
+    pub at_bats: bool,
+    /// Emit JSON without spaces between tokens (`{"a":1}` rather than `{ "a": 1 }`).
+    pub compact: bool,
+    /// Emit the defensive alignment of each batted ball in its hit data.
+    pub alignment: bool,
+    /// Leave out the movement lines of the plays, for models that do not need the base running.
+    pub no_movements: bool,
+    /// How to emit the fielders credited in each play.
+    pub fielders: FielderFormat,
+    /// Emit keys under these names instead (e.g. [`SHORT_KEY_NAMES`]). Keys not in the map are unchanged.
+    pub key_names: std::collections::HashMap<String, String>,
+    /// How to turn the preprocessed lines into text ([`JsonFormatter`] if not given).
+    pub formatter: Option<std::sync::Arc<dyn Formatter>>,
+    /// Tokens to emit in place of player names, by name. Built per game when `anonymize` or `player_ids` is set.
+    pub player_names: Option<std::collections::HashMap<String, String>>,
 }
 
-fn log(message: String) {
-    let _ = std::fs::create_dir("data");
+impl PreprocessOptions {
+    /// Whether the play passes the play type and inning filters. Types match either as written in the output
+    /// (`Home Run`) or as variant names (`HomeRun`).
+    fn includes(&self, play: &Play) -> bool {
+        let play_type = play.play_type().replace(' ', "");
+        let matches = |play_types: &[String]| play_types.iter().any(|t| t.replace(' ', "") == play_type);
+        let inning = play.inning().number;
+
+        self.include_types.as_deref().is_none_or(matches)
+            && !matches(&self.exclude_types)
+            && self.min_inning.is_none_or(|min_inning| inning >= min_inning)
+            && self.max_inning.is_none_or(|max_inning| inning <= max_inning)
+    }
+
+    /// The formatter to turn the preprocessed lines into text with.
+    fn formatter(&self) -> &dyn Formatter {
+        self.formatter.as_deref().unwrap_or(&JsonFormatter)
+    }
+
+    /// The token to emit for the player with the given name.
+    fn player<'a>(&'a self, name: &'a str) -> &'a str {
+        match self.player_names.as_ref().and_then(|player_names| player_names.get(name)) {
+            Some(token) => token,
+            None if self.anonymize.is_some() => "UNKNOWN_PLAYER", // never leak a name that has no pseudonym
+            None => name,
+        }
+    }
+
+    /// Free text with player names replaced by their pseudonyms, if anonymizing.
+    fn text(&self, text: &str) -> String {
+        match (&self.anonymize, &self.player_names) {
+            (Some(_), Some(player_names)) => {
+                // longest names first, so a name is never partly replaced as part of a shorter one
+                let mut player_names = player_names.iter().collect::<Vec<_>>();
+                player_names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+                player_names
+                    .into_iter()
+                    .fold(text.to_string(), |text, (name, pseudonym)| text.replace(name.as_str(), pseudonym))
+            },
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// The version of the preprocessed output. Bump it whenever a change to the preprocessing changes the output,
+/// so that `preprocess` preprocesses every game again.
+pub const PREPROCESS_VERSION: u32 = 9;
+
+pub trait Preprocess {
+    /// Returns the object as JSON, preprocessed according to `options`. Transforms of the output (e.g. renaming
+    /// keys) work on this rather than on the text.
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value;
+
+    /// Returns a string representing the object, preprocessed according to `options` and formatted by its formatter.
+    fn preprocess(&self, options: &PreprocessOptions) -> String {
+        options.formatter().format_line(&self.to_value(options), options)
+    }
+}
 
-    let mut file = std::fs::OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open("data/log.txt")
-        .unwrap();
+/// The names of the players already looked up or added with [`cache_player_names`], by id, so that each is requested
+/// at most once, however many games are got at once.
+static PLAYER_NAMES: std::sync::LazyLock<std::sync::RwLock<std::collections::HashMap<usize, String>>> =
+    std::sync::LazyLock::new(Default::default);
 
-    writeln!(file, "{}", message).unwrap();
+/// Add players whose names are known without requesting them (e.g. from season rosters) to the names looked up by id.
+pub fn cache_player_names(names: impl IntoIterator<Item = (usize, String)>) {
+    PLAYER_NAMES.write().unwrap().extend(names);
 }
 
 async fn get_player_name_from_id(player_id: usize) -> Result<String, String> {
+    let cached = PLAYER_NAMES.read().unwrap().get(&player_id).cloned();
+    if let Some(player_name) = cached {
+        return Ok(player_name);
+    }
+
     let url = format!("https://statsapi.mlb.com/api/v1/people/{player_id}");
-    let response = if let Ok(response) = reqwest::get(&url).await {
-        response
-    } else {
-        return Err("Failed to get player data".to_string());
-    };
-    let player_data = if let Ok(player_data) = response.json::<serde_json::Value>().await {
-        player_data
-    } else {
-        return Err("Failed to parse player data".to_string());
-    };
-    let player_name = player_data["people"][0]["fullName"].as_str().unwrap().to_string();
+    let player_data = crate::api::get_json(&url)
+        .await
+        .map_err(|e| format!("Failed to get player data: {e}"))?;
+    let player_name = player_data["people"][0]["fullName"].as_str().ok_or("No player name")?.to_string();
+    PLAYER_NAMES.write().unwrap().insert(player_id, player_name.clone());
 
     Ok(player_name)
 }
 
-fn base_value_to_option_u8(base: &serde_json::Value) -> Option<u8> {
+fn base_value_to_option_u8(base: &serde_json::Value) -> Result<Option<u8>, String> {
     if base.is_null() {
-        return None;
+        return Ok(None);
     }
 
-    match base.as_str().unwrap() {
-        "1B" => Some(1),
-        "2B" => Some(2),
-        "3B" => Some(3),
-        "4B" | "score" => Some(4),
-        _ => panic!("Unknown base value: {}", base),
+    match base.as_str() {
+        Some("1B") => Ok(Some(1)),
+        Some("2B") => Ok(Some(2)),
+        Some("3B") => Ok(Some(3)),
+        Some("4B" | "score") => Ok(Some(4)),
+        _ => Err(format!("Unknown base value: {}", base)),
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Date {
     year: u16,
     month: u8,
     day: u8,
 }
 
-impl ToString for Date {
-    fn to_string(&self) -> String {
-        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
     }
 }
 
-impl From<&str> for Date {
-    fn from(date_str: &str) -> Self {
-        let date_parts: Vec<&str> = date_str.split("-").collect();
+impl TryFrom<&str> for Date {
+    type Error = String;
+
+    /// Parse a date in the format `YYYY-MM-DD`.
+    fn try_from(date_str: &str) -> Result<Self, Self::Error> {
+        let date_parts: Vec<&str> = date_str.split('-').collect();
+        let [year, month, day] = date_parts[..] else {
+            return Err(format!("Invalid date: {date_str}"));
+        };
 
-        Date {
-            year: date_parts[0].parse().unwrap(),
-            month: date_parts[1].parse().unwrap(),
-            day: date_parts[2].parse().unwrap(),
+        let year = year.parse().map_err(|_| format!("Invalid year in date: {date_str}"))?;
+        let month = month.parse().map_err(|_| format!("Invalid month in date: {date_str}"))?;
+        let day = day.parse().map_err(|_| format!("Invalid day in date: {date_str}"))?;
+        if !(1..=12).contains(&month) {
+            return Err(format!("Month out of range in date: {date_str}"));
+        }
+        if !(1..=31).contains(&day) {
+            return Err(format!("Day out of range in date: {date_str}"));
         }
+
+        Ok(Date { year, month, day })
     }
 }
 
@@ -99,7 +300,12 @@ pub enum Position {
 
 impl Position {
     pub fn from_abbr(position_abbr: &str) -> Self {
-        match position_abbr {
+        Position::try_from_abbr(position_abbr).unwrap_or_else(|| panic!("Unknown position abbreviation: {}", position_abbr))
+    }
+
+    /// The position with the given abbreviation, if it is known.
+    pub fn try_from_abbr(position_abbr: &str) -> Option<Self> {
+        let position = match position_abbr {
             "P" | "LHP" | "RHP" => Position::Pitcher,
             "C" => Position::Catcher,
             "1B" => Position::FirstBase,
@@ -118,14 +324,16 @@ impl Position {
             "UT" | "UTIL" => Position::Utility,
             "RP" => Position::ReliefPitcher,
             "SP" => Position::StartingPitcher,
-            _ => panic!("Unknown position abbreviation: {}", position_abbr),
-        }
+            _ => return None,
+        };
+
+        Some(position)
     }
 }
 
-impl ToString for Position {
-    fn to_string(&self) -> String {
-        match self {
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let position = match self {
             Position::Pitcher => "PITCHER",
             Position::Catcher => "CATCHER",
             Position::FirstBase => "FIRST_BASE",
@@ -144,44 +352,100 @@ impl ToString for Position {
             Position::Utility => "UTILITY",
             Position::ReliefPitcher => "RELIEF_PITCHER",
             Position::StartingPitcher => "STARTING_PITCHER",
-        }
-        .to_string()
+        };
+
+        write!(f, "{position}")
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Player {
-    name: String,
-    position: Position,
+    /// The player's MLB id (`person.id` in the boxscore), or 0 for games saved before ids were recorded.
+    #[serde(default)]
+    pub id: usize,
+    pub name: String,
+    pub position: Position,
 }
 
 impl Player {
-    pub async fn new(name: String, position: Position) -> Result<Self, String> {
-        Ok(Self { name, position })
+    pub async fn new(id: usize, name: String, position: Position) -> Result<Self, String> {
+        Ok(Self { id, name, position })
+    }
+
+    /// The id, name and primary position (if it is known) of every player on the team's roster at any point in the season.
+    pub async fn all_on_season_roster(team_id: u32, season: u16) -> Result<Vec<(usize, String, Option<Position>)>, String> {
+        let url = format!("https://statsapi.mlb.com/api/v1/teams/{team_id}/roster?rosterType=fullSeason&season={season}");
+        let roster_data = crate::api::get_json(&url)
+            .await
+            .map_err(|e| format!("Failed to get roster: {e}"))?;
+
+        roster_data["roster"]
+            .as_array()
+            .ok_or("No roster")?
+            .iter()
+            .map(|entry| {
+                let id = entry["person"]["id"].as_u64().ok_or("No player id")? as usize;
+                let name = entry["person"]["fullName"].as_str().ok_or("No player name")?.to_string();
+                let position = entry["position"]["abbreviation"].as_str().and_then(Position::try_from_abbr);
+
+                Ok((id, name, position))
+            })
+            .collect()
     }
 }
 
 impl Preprocess for Player {
-    fn preprocess(&self) -> String {
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value {
         // format!("[{}] {}", self.position.to_string(), self.name)
-        format!("{{ \"position\": \"{}\", \"name\": \"{}\" }}", self.position.to_string(), self.name)
+        if options.anonymize.is_some() {
+            // the id would identify the player
+            return serde_json::json!({ "position": self.position.to_string(), "name": options.player(&self.name) });
+        }
+
+        serde_json::json!({ "id": self.id, "position": self.position.to_string(), "name": options.player(&self.name) })
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Team {
-    pub id: u8,
-    players: Vec<Player>,
+    pub id: u32,
+    pub players: Vec<Player>,
 }
 
 impl Team {
+    /// The ids of the teams of the given sport that played in the given season.
+    pub async fn ids_in_season(sport_id: u32, season: u16) -> Result<Vec<u32>, String> {
+        let url = format!("https://statsapi.mlb.com/api/v1/teams?sportId={sport_id}&season={season}");
+        let teams_data = crate::api::get_json(&url)
+            .await
+            .map_err(|e| format!("Failed to get teams: {e}"))?;
+
+        let mut team_ids = teams_data["teams"]
+            .as_array()
+            .ok_or("No teams")?
+            .iter()
+            .map(|team| team["id"].as_u64().map(|id| id as u32).ok_or("No team id".to_string()))
+            .collect::<Result<Vec<u32>, String>>()?;
+        team_ids.sort();
+
+        Ok(team_ids)
+    }
+
     pub async fn from_boxscore_team_data_and_date(team_data: &serde_json::Value) -> Result<Self, String> {
-        let id = team_data["team"]["id"].as_u64().unwrap() as u8;
-        let players_data = team_data["players"].as_object().unwrap();
+        let id = team_data["team"]["id"].as_u64().ok_or("No team id")? as u32;
+        let players_data = team_data["players"].as_object().ok_or("No players")?;
+
+        // in order of their keys (`ID{player_id}`) rather than the order of the boxscore
+        let mut players_data = players_data.iter().collect::<Vec<_>>();
+        players_data.sort_by_key(|(key, _)| key.as_str());
 
         let mut players = Vec::new();
-        for player_data in players_data.values() {
-            let player_name = player_data["person"]["fullName"].as_str().unwrap().to_string();
+        for (_, player_data) in players_data {
+            let player_id = match player_data["person"]["id"].as_u64() {
+                Some(id) => id as usize,
+                None => return Err("No player id".to_string()),
+            };
+            let player_name = player_data["person"]["fullName"].as_str().ok_or("No player name")?.to_string();
             let position_abbr = if let Some(abbr) = player_data["position"]["abbreviation"].as_str() {
                 abbr
             } else {
@@ -189,7 +453,7 @@ impl Team {
             };
             let position = Position::from_abbr(position_abbr);
 
-            let player = Player::new(player_name, position).await?;
+            let player = Player::new(player_id, player_name, position).await?;
 
             players.push(player);
         }
@@ -202,7 +466,7 @@ impl Team {
 }
 
 impl Preprocess for Team {
-    fn preprocess(&self) -> String {
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value {
         // let mut tokens = String::new();
 
         // tokens += &format!("[TEAM] {}\n", self.id);
@@ -212,15 +476,45 @@ impl Preprocess for Team {
 
         // tokens
 
-        format!(
-            "{{ \"id\": {}, \"players\": [{}] }}",
-            self.id,
-            self.players.iter().map(|player| player.preprocess()).collect::<Vec<String>>().join(", "),
-        )
+        serde_json::json!({
+            "id": self.id,
+            "players": self.players.iter().map(|player| player.to_value(options)).collect::<Vec<_>>(),
+        })
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+pub struct Location {
+    city: String,
+    state: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+impl Location {
+    /// Parse a venue's location from the `location` object of a venue in the live feed.
+    pub fn from_value(value: &serde_json::Value) -> Option<Self> {
+        let city = value["city"].as_str()?.to_string();
+        let state = value["state"].as_str().map(str::to_string);
+        let latitude = value["defaultCoordinates"]["latitude"].as_f64();
+        let longitude = value["defaultCoordinates"]["longitude"].as_f64();
+
+        Some(Self {
+            city,
+            state,
+            latitude,
+            longitude,
+        })
+    }
+}
+
+impl Preprocess for Location {
+    fn to_value(&self, _options: &PreprocessOptions) -> serde_json::Value {
+        serde_json::json!({ "city": self.city, "state": self.state, "latitude": self.latitude, "longitude": self.longitude })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Weather {
     condition: String,
     temperature: u8,
@@ -235,7 +529,7 @@ impl Weather {
             .as_str();
 
         let wind_speed = if let Some(wind_speed) = wind_speed {
-            wind_speed.to_string()
+            wind_speed
                 .split(' ')
                 .collect::<Vec<&str>>()
                 .first()
@@ -255,14 +549,335 @@ impl Weather {
 }
 
 impl Preprocess for Weather {
-    fn preprocess(&self) -> String {
+    fn to_value(&self, _options: &PreprocessOptions) -> serde_json::Value {
         // format!("[WEATHER] {} {} {}", self.condition, self.temperature, self.wind_speed)
-        format!(
-            "{{ \"condition\": \"{}\", \"temperature\": {}, \"wind_speed\": {} }}",
-            self.condition,
-            self.temperature,
-            self.wind_speed,
-        )
+        serde_json::json!({ "condition": self.condition, "temperature": self.temperature, "wind_speed": self.wind_speed })
+    }
+}
+
+/// Format preprocessed JSON as text: `{ "a": 1, "b": [1, 2] }`, or `{"a":1,"b":[1,2]}` if `compact`.
+/// Floats are written as Rust writes them, so whole numbers have no fractional part (`95` rather than `95.0`).
+fn format_json(value: &serde_json::Value, compact: bool) -> String {
+    let (separator, colon, padding) = if compact { (",", ":", "") } else { (", ", ": ", " ") };
+
+    match value {
+        serde_json::Value::Array(items) => {
+            format!("[{}]", items.iter().map(|item| format_json(item, compact)).collect::<Vec<String>>().join(separator))
+        },
+        serde_json::Value::Object(fields) if fields.is_empty() => "{}".to_string(),
+        serde_json::Value::Object(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(key, value)| format!("{}{colon}{}", serde_json::Value::from(key.as_str()), format_json(value, compact)))
+                .collect::<Vec<String>>();
+            format!("{{{padding}{}{padding}}}", fields.join(separator))
+        },
+        serde_json::Value::Number(number) if number.is_f64() => number.as_f64().unwrap().to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// Turns preprocessed objects (see [`Preprocess::to_value`]) into text, so that the same lines can be written in
+/// whichever style a consumer wants.
+pub trait Formatter: std::fmt::Debug + Send + Sync {
+    /// Format a single preprocessed line, e.g. a game's context.
+    fn format_line(&self, line: &serde_json::Value, options: &PreprocessOptions) -> String;
+
+    /// Format the lines of a play, one per line.
+    fn format_play(&self, play: &Play, options: &PreprocessOptions) -> String {
+        format_lines(self, &play.to_value(options), options)
+    }
+
+    /// Format the lines of a game, one per line and ending with a newline.
+    fn format_game(&self, game: &Game, options: &PreprocessOptions) -> String {
+        format!("{}\n", format_lines(self, &game.to_value(options), options))
+    }
+}
+
+/// Format preprocessed lines (a JSON array of them) with `formatter`, one per line.
+fn format_lines<F: Formatter + ?Sized>(formatter: &F, lines: &serde_json::Value, options: &PreprocessOptions) -> String {
+    lines
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|line| formatter.format_line(line, options))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Formats each line as JSON, with spaces between tokens unless `compact` (the default).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn format_line(&self, line: &serde_json::Value, options: &PreprocessOptions) -> String {
+        format_json(line, options.compact)
+    }
+}
+
+/// Formats each line as flat text, with the path of each value before it and strings unquoted
+/// (e.g. `inning.number: 3, inning.top: true, type: Single`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextFormatter;
+
+impl TextFormatter {
+    /// The `path: value` pairs of `value`, with the keys of nested objects joined by dots.
+    fn fields(path: &str, value: &serde_json::Value, fields: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(object) if !object.is_empty() => {
+                for (key, value) in object {
+                    let path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                    Self::fields(&path, value, fields);
+                }
+            },
+            value => fields.push(format!("{path}: {}", Self::value(value))),
+        }
+    }
+
+    /// A value inside a line, with objects in arrays in parentheses.
+    fn value(value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Array(items) => format!("[{}]", items.iter().map(Self::value).collect::<Vec<String>>().join(", ")),
+            serde_json::Value::Object(object) if object.is_empty() => "()".to_string(),
+            serde_json::Value::Object(_) => {
+                let mut fields = Vec::new();
+                Self::fields("", value, &mut fields);
+                format!("({})", fields.join(", "))
+            },
+            serde_json::Value::Number(number) if number.is_f64() => number.as_f64().unwrap().to_string(),
+            value => value.to_string(),
+        }
+    }
+}
+
+impl Formatter for TextFormatter {
+    fn format_line(&self, line: &serde_json::Value, _options: &PreprocessOptions) -> String {
+        match line {
+            serde_json::Value::Object(_) => {
+                let mut fields = Vec::new();
+                Self::fields("", line, &mut fields);
+                fields.join(", ")
+            },
+            line => Self::value(line),
+        }
+    }
+}
+
+/// Apply `key` to every key of the JSON objects in `value` and `string` to every string that is not a key.
+fn map_json(value: serde_json::Value, key: &impl Fn(String) -> String, string: &impl Fn(String) -> String) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => {
+            fields.into_iter().map(|(name, value)| (key(name), map_json(value, key, string))).collect()
+        },
+        serde_json::Value::Array(items) => items.into_iter().map(|item| map_json(item, key, string)).collect(),
+        serde_json::Value::String(text) => serde_json::Value::String(string(text)),
+        value => value,
+    }
+}
+
+/// Rename the keys of the JSON objects in `value` according to `key_names`, leaving everything else as it is.
+fn rename_keys(value: serde_json::Value, key_names: &std::collections::HashMap<String, String>) -> serde_json::Value {
+    map_json(value, &|name| key_names.get(&name).cloned().unwrap_or(name), &|text| text)
+}
+
+/// `value` with every key and string in lowercase.
+fn lowercase_json(value: serde_json::Value) -> serde_json::Value {
+    map_json(value, &|name| name.to_lowercase(), &|text| text.to_lowercase())
+}
+
+/// The runs scored by each team in one inning of the line score.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InningScore {
+    pub number: u8,
+    pub home_runs: Option<u8>,
+    pub away_runs: Option<u8>,
+}
+
+impl InningScore {
+    pub fn from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let number = match value["num"].as_u64() {
+            Some(number) => number as u8,
+            None => return Err("No inning number in line score".to_string()),
+        };
+        let home_runs = value["home"]["runs"].as_u64().map(|runs| runs as u8);
+        let away_runs = value["away"]["runs"].as_u64().map(|runs| runs as u8);
+
+        Ok(Self {
+            number,
+            home_runs,
+            away_runs,
+        })
+    }
+
+    /// Parse the innings of the feed's `liveData.linescore`. A missing line score is treated as empty.
+    pub fn line_score_from_value(linescore: &serde_json::Value) -> Result<Vec<Self>, String> {
+        match linescore["innings"].as_array() {
+            Some(innings) => innings.iter().map(InningScore::from_value).collect(),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl Preprocess for InningScore {
+    fn to_value(&self, _options: &PreprocessOptions) -> serde_json::Value {
+        serde_json::json!({ "number": self.number, "home_runs": self.home_runs, "away_runs": self.away_runs })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Winner {
+    Home,
+    Away,
+}
+
+impl Winner {
+    /// The winner given the final scores, or `None` for a tie.
+    pub fn from_scores(home_score: u8, away_score: u8) -> Option<Self> {
+        match home_score.cmp(&away_score) {
+            std::cmp::Ordering::Greater => Some(Winner::Home),
+            std::cmp::Ordering::Less => Some(Winner::Away),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Winner::Home => "home",
+            Winner::Away => "away",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum UmpirePosition {
+    HomePlate,
+    FirstBase,
+    SecondBase,
+    ThirdBase,
+    LeftField,
+    RightField,
+}
+
+impl UmpirePosition {
+    /// Parse the boxscore's `officialType` (e.g. `Home Plate`).
+    pub fn from_official_type(official_type: &str) -> Option<Self> {
+        match official_type {
+            "Home Plate" => Some(UmpirePosition::HomePlate),
+            "First Base" => Some(UmpirePosition::FirstBase),
+            "Second Base" => Some(UmpirePosition::SecondBase),
+            "Third Base" => Some(UmpirePosition::ThirdBase),
+            "Left Field" => Some(UmpirePosition::LeftField),
+            "Right Field" => Some(UmpirePosition::RightField),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UmpirePosition::HomePlate => "HP",
+            UmpirePosition::FirstBase => "1B",
+            UmpirePosition::SecondBase => "2B",
+            UmpirePosition::ThirdBase => "3B",
+            UmpirePosition::LeftField => "LF",
+            UmpirePosition::RightField => "RF",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Umpire {
+    pub name: String,
+    pub position: UmpirePosition,
+}
+
+impl Umpire {
+    /// Parse the umpires from the boxscore's `officials`, skipping any with an unknown position.
+    pub fn all_from_officials(officials: &serde_json::Value) -> Vec<Self> {
+        officials
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|official| Some(Umpire {
+                name: official["official"]["fullName"].as_str()?.to_string(),
+                position: UmpirePosition::from_official_type(official["officialType"].as_str()?)?,
+            }))
+            .collect()
+    }
+}
+
+impl Preprocess for Umpire {
+    fn to_value(&self, _options: &PreprocessOptions) -> serde_json::Value {
+        serde_json::json!({ "position": self.position.as_str(), "name": self.name })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DayNight {
+    Day,
+    Night,
+}
+
+impl DayNight {
+    /// Parse the feed's `dayNight` code (`day` or `night`).
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "day" => Some(DayNight::Day),
+            "night" => Some(DayNight::Night),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DayNight::Day => "day",
+            DayNight::Night => "night",
+        }
+    }
+}
+
+/// The part of the season a game was played in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameType {
+    #[default]
+    Regular,
+    SpringTraining,
+    Postseason,
+    AllStar,
+    Exhibition,
+}
+
+impl GameType {
+    pub const ALL: [GameType; 5] = [
+        GameType::Regular,
+        GameType::SpringTraining,
+        GameType::Postseason,
+        GameType::AllStar,
+        GameType::Exhibition,
+    ];
+
+    /// Parse the feed's `game.type` code. Every postseason round (wild card, division series, ...) is `Postseason`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "R" => Some(GameType::Regular),
+            "S" => Some(GameType::SpringTraining),
+            "F" | "D" | "L" | "W" | "C" | "P" => Some(GameType::Postseason),
+            "A" => Some(GameType::AllStar),
+            "E" => Some(GameType::Exhibition),
+            _ => None,
+        }
+    }
+
+    /// The directory games of this type are saved in, between the year and the home team id.
+    /// Regular season games are saved directly in the year's directory.
+    pub fn dir_name(&self) -> Option<&'static str> {
+        match self {
+            GameType::Regular => None,
+            GameType::SpringTraining => Some("spring_training"),
+            GameType::Postseason => Some("postseason"),
+            GameType::AllStar => Some("all_star"),
+            GameType::Exhibition => Some("exhibition"),
+        }
     }
 }
 
@@ -274,9 +889,92 @@ pub struct GameContext {
     weather: Weather,
     pub home_team: Team,
     pub away_team: Team,
+    /// Whether the game was still in progress when it was fetched, so its plays are incomplete.
+    #[serde(default)]
+    pub in_progress: bool,
+    #[serde(default)]
+    pub home_final_score: Option<u8>,
+    #[serde(default)]
+    pub away_final_score: Option<u8>,
+    /// The winning team, or `None` for a tie or a game in progress.
+    #[serde(default)]
+    pub winner: Option<Winner>,
+    /// The pitchers credited with the win, the loss and the save, from the feed's decisions. `None` for a game
+    /// in progress or without a decision (e.g. a tie, or a win with no save).
+    #[serde(default)]
+    pub winning_pitcher: Option<String>,
+    #[serde(default)]
+    pub losing_pitcher: Option<String>,
+    #[serde(default)]
+    pub save_pitcher: Option<String>,
+    /// The runs scored by each team in each inning.
+    #[serde(default)]
+    pub line_score: Vec<InningScore>,
+    /// The date play resumed on, if the game was suspended and finished on a later date than `date`.
+    #[serde(default)]
+    pub resumed_date: Option<Date>,
+    /// The venue's MLB id, which unlike its name does not change with sponsorships (0 for games saved before it was recorded).
+    #[serde(default)]
+    pub venue_id: usize,
+    #[serde(default)]
+    pub location: Option<Location>,
+    #[serde(default)]
+    pub day_night: Option<DayNight>,
+    /// The scheduled start time, in UTC (e.g. `2021-04-01T17:05:00Z`).
+    #[serde(default)]
+    pub start_time: Option<String>,
+    #[serde(default)]
+    pub umpires: Vec<Umpire>,
+    /// Games saved before the game type was recorded are assumed to be regular season games.
+    #[serde(default)]
+    pub game_type: GameType,
 }
 
 impl GameContext {
+    /// A context with only what every game has; everything else is unknown (or empty).
+    fn new(game_pk: usize, date: Date, venue_name: String, weather: Weather, home_team: Team, away_team: Team) -> Self {
+        Self {
+            game_pk,
+            date,
+            venue_name,
+            weather,
+            home_team,
+            away_team,
+            in_progress: false,
+            home_final_score: None,
+            away_final_score: None,
+            winner: None,
+            winning_pitcher: None,
+            losing_pitcher: None,
+            save_pitcher: None,
+            line_score: Vec::new(),
+            resumed_date: None,
+            venue_id: 0,
+            location: None,
+            day_night: None,
+            start_time: None,
+            umpires: Vec::new(),
+            game_type: GameType::Regular,
+        }
+    }
+
+    /// A context from the live feed alone, for a game whose boxscore could not be got: the teams have no players,
+    /// and there are no final scores or umpires.
+    pub fn from_feed_data_and_date_and_weather_and_game_pk(
+        game_data: &serde_json::Value,
+        game_date: Date,
+        weather: Weather,
+        game_pk: usize,
+    ) -> Result<Self, String> {
+        let team = |side: &str| -> Result<Team, String> {
+            let id = game_data["gameData"]["teams"][side]["id"].as_u64().ok_or(format!("No {side} team id"))? as u32;
+            Ok(Team { id, players: Vec::new() })
+        };
+        let venue_name = game_data["gameData"]["venue"]["name"].as_str().ok_or("No venue name")?.to_string();
+
+        Ok(Self::new(game_pk, game_date, venue_name, weather, team("home")?, team("away")?))
+    }
+
     pub async fn from_game_boxscore_data_and_date_and_weather_and_game_pk(
         game_data: &serde_json::Value,
         game_date: Date,
@@ -285,24 +983,32 @@ impl GameContext {
     ) -> Result<Self, String> {
         let home_team_data = &game_data["teams"]["home"];
         let home_team = Team::from_boxscore_team_data_and_date(home_team_data).await?;
-        let venue_name = home_team_data["team"]["venue"]["name"].as_str().unwrap().to_string();
+        let venue_name = home_team_data["team"]["venue"]["name"].as_str().ok_or("No venue name")?.to_string();
+        let venue_id = home_team_data["team"]["venue"]["id"].as_u64().unwrap_or(0) as usize;
 
         let away_team_data = &game_data["teams"]["away"];
         let away_team = Team::from_boxscore_team_data_and_date(away_team_data).await?;
 
+        let home_final_score = home_team_data["teamStats"]["batting"]["runs"].as_u64().map(|runs| runs as u8);
+        let away_final_score = away_team_data["teamStats"]["batting"]["runs"].as_u64().map(|runs| runs as u8);
+        let winner = match (home_final_score, away_final_score) {
+            (Some(home_score), Some(away_score)) => Winner::from_scores(home_score, away_score),
+            _ => None,
+        };
+
         Ok(Self {
-            game_pk,
-            date: game_date,
-            venue_name,
-            weather,
-            home_team,
-            away_team,
+            home_final_score,
+            away_final_score,
+            winner,
+            venue_id,
+            umpires: Umpire::all_from_officials(&game_data["officials"]),
+            ..Self::new(game_pk, game_date, venue_name, weather, home_team, away_team)
         })
     }
 }
 
 impl Preprocess for GameContext {
-    fn preprocess(&self) -> String {
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value {
         // format!(
         //     "{} [DATE] {} [VENUE] {} {}\n\n{}\n{}",
         //     self.game_pk,
@@ -312,15 +1018,28 @@ impl Preprocess for GameContext {
         //     self.home_team.preprocess(),
         //     self.away_team.preprocess(),
         // )
-        format!(
-            "{{ \"game_pk\": {}, \"date\": \"{}\", \"venue_name\": \"{}\", \"weather\": {}, \"home_team\": {}, \"away_team\": {} }}",
-            self.game_pk,
-            self.date.to_string(),
-            self.venue_name,
-            self.weather.preprocess(),
-            self.home_team.preprocess(),
-            self.away_team.preprocess(),
-        )
+        serde_json::json!({
+            "game_pk": self.game_pk,
+            "date": self.date.to_string(),
+            "venue_name": self.venue_name,
+            "weather": self.weather.to_value(options),
+            "home_team": self.home_team.to_value(options),
+            "away_team": self.away_team.to_value(options),
+            "in_progress": self.in_progress,
+            "home_final_score": self.home_final_score,
+            "away_final_score": self.away_final_score,
+            "winner": self.winner.map(|winner| winner.as_str()),
+            "winning_pitcher": self.winning_pitcher.as_deref().map(|name| options.player(name)),
+            "losing_pitcher": self.losing_pitcher.as_deref().map(|name| options.player(name)),
+            "save_pitcher": self.save_pitcher.as_deref().map(|name| options.player(name)),
+            "line_score": self.line_score.iter().map(|inning| inning.to_value(options)).collect::<Vec<_>>(),
+            "resumed_date": self.resumed_date.map(|date| date.to_string()),
+            "venue_id": self.venue_id,
+            "location": self.location.as_ref().map(|location| location.to_value(options)),
+            "day_night": self.day_night.map(|day_night| day_night.as_str()),
+            "start_time": self.start_time,
+            "umpires": self.umpires.iter().map(|umpire| umpire.to_value(options)).collect::<Vec<_>>(),
+        })
     }
 }
 
@@ -333,22 +1052,22 @@ pub struct Movement {
 }
 
 impl Movement {
-    pub fn from_runner_and_value(runner: String, movement_value: &serde_json::Value) -> Self {
-        let start_base = base_value_to_option_u8(&movement_value["start"]);
-        let end_base = base_value_to_option_u8(&movement_value["end"]);
+    pub fn from_runner_and_value(runner: String, movement_value: &serde_json::Value) -> Result<Self, String> {
+        let start_base = base_value_to_option_u8(&movement_value["start"])?;
+        let end_base = base_value_to_option_u8(&movement_value["end"])?;
         let is_out = movement_value["isOut"].as_bool().unwrap_or(false);
 
-        Movement {
+        Ok(Movement {
             runner,
             start_base,
             end_base,
             is_out,
-        }
+        })
     }
 }
 
 impl Preprocess for Movement {
-    fn preprocess(&self) -> String {
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value {
         // let mut tokens = String::new();
 
         // tokens += &format!("{} ", self.runner);
@@ -384,68 +1103,289 @@ impl Preprocess for Movement {
             }
         }
 
-        format!(
-            "{{ \"runner\": \"{}\", \"start_base\": \"{}\", \"end_base\": \"{}\", \"is_out\": {} }}",
-            self.runner,
-            start_base,
-            end_base,
-            self.is_out,
-        )
+        serde_json::json!({
+            "runner": options.player(&self.runner),
+            "start_base": start_base,
+            "end_base": end_base,
+            "is_out": self.is_out,
+        })
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Inning {
-    pub number: u8,
-    pub top: bool,
+/// Statcast measurements of a batted ball, each missing if it was not tracked.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HitData {
+    /// In miles per hour.
+    exit_velocity: Option<f64>,
+    /// In degrees above horizontal.
+    launch_angle: Option<f64>,
+    /// In feet.
+    total_distance: Option<f64>,
+    /// How the defense was aligned (e.g. for a shift), as the feed's fields that describe it, by name.
+    /// Missing if the feed does not describe it, as is the case for most games.
+    #[serde(default)]
+    alignment: Option<std::collections::BTreeMap<String, String>>,
 }
 
-impl Inning {
+impl HitData {
+    /// The hit data of the last pitch of the play, which is the one put in play.
     pub fn from_value(value: &serde_json::Value) -> Self {
-        let number = value["inning"].as_u64().unwrap() as u8;
-        let top = value["isTopInning"].as_bool().unwrap();
+        let event = value["playEvents"]
+            .as_array()
+            .and_then(|events| events.iter().rev().find(|event| event["isPitch"].as_bool() == Some(true)));
+
+        match event {
+            Some(event) => Self {
+                exit_velocity: event["hitData"]["launchSpeed"].as_f64(),
+                launch_angle: event["hitData"]["launchAngle"].as_f64(),
+                total_distance: event["hitData"]["totalDistance"].as_f64(),
+                alignment: HitData::alignment_from_values(&[&event["details"], &event["hitData"], &value["matchup"]]),
+            },
+            None => Self::default(),
+        }
+    }
 
-        Self { number, top }
+    /// The string fields of the given objects whose names mention an alignment or a shift, by name.
+    /// The feed only describes the defensive alignment for some seasons, and not always in the same place or
+    /// under the same name, so this looks for it wherever it may be.
+    fn alignment_from_values(values: &[&serde_json::Value]) -> Option<std::collections::BTreeMap<String, String>> {
+        let alignment = values
+            .iter()
+            .filter_map(|value| value.as_object())
+            .flatten()
+            .filter(|(name, _)| {
+                let name = name.to_lowercase();
+                name.contains("alignment") || name.contains("shift")
+            })
+            .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+            .collect::<std::collections::BTreeMap<String, String>>();
+
+        if alignment.is_empty() { None } else { Some(alignment) }
     }
 }
 
-impl ToString for Inning {
-    fn to_string(&self) -> String {
-        format!("{} {}", self.number, if self.top { "top" } else { "bottom" })
+impl Preprocess for HitData {
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value {
+        let mut value = serde_json::json!({
+            "exit_velocity": self.exit_velocity,
+            "launch_angle": self.launch_angle,
+            "total_distance": self.total_distance,
+        });
+        if options.alignment {
+            value["alignment"] = serde_json::json!(self.alignment);
+        }
+
+        value
     }
 }
 
-impl Preprocess for Inning {
-    fn preprocess(&self) -> String {
-        format!("{{ \"number\": {}, \"top\": {} }}", self.number, self.top)
+/// The result of a pitch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PitchCall {
+    Ball,
+    Strike,
+    Foul,
+    InPlay,
+    HitByPitch,
+    /// A ball thrown wide on purpose so the catcher can throw out a runner who is stealing.
+    Pitchout,
+    /// A ball thrown wide on purpose to walk the batter (before automatic intentional walks, and in some leagues still).
+    IntentionalBall,
+}
+
+impl PitchCall {
+    /// The call of a pitch from its `details`, by its call code, or by its ball/strike/in play flags
+    /// for codes that are not known.
+    fn from_details(details: &serde_json::Value) -> Option<Self> {
+        let call = match details["call"]["code"].as_str()? {
+            "B" | "*B" | "V" => PitchCall::Ball,
+            "P" => PitchCall::Pitchout,
+            "I" => PitchCall::IntentionalBall,
+            "C" | "S" | "W" | "T" | "M" | "A" | "Q" | "O" => PitchCall::Strike,
+            "F" | "L" | "R" => PitchCall::Foul,
+            "X" | "D" | "E" => PitchCall::InPlay,
+            "H" => PitchCall::HitByPitch,
+            _ if details["isInPlay"].as_bool() == Some(true) => PitchCall::InPlay,
+            _ if details["isStrike"].as_bool() == Some(true) => PitchCall::Strike,
+            _ if details["isBall"].as_bool() == Some(true) => PitchCall::Ball,
+            _ => return None,
+        };
+
+        Some(call)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            PitchCall::Ball => "Ball",
+            PitchCall::Strike => "Strike",
+            PitchCall::Foul => "Foul",
+            PitchCall::InPlay => "In Play",
+            PitchCall::HitByPitch => "Hit By Pitch",
+            PitchCall::Pitchout => "Pitchout",
+            PitchCall::IntentionalBall => "Intentional Ball",
+        }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum Play {
-    // outs
-    Groundout {
-        inning: Inning,
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
-        movements: Vec<Movement>,
-    },
-    BuntGroundout {
-        inning: Inning,
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
-        movements: Vec<Movement>,
-    },
-    Strikeout {
-        inning: Inning,
-        batter: String,
-        pitcher: String,
-        movements: Vec<Movement>,
-    },
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pitch {
+    pub call: PitchCall,
+    /// The kind of pitch (e.g. `Slider`), if it was tracked.
+    pub pitch_type: Option<String>,
+}
+
+impl Pitch {
+    /// The pitches thrown during a play, in order. Pitches with a call that is not known are left out.
+    pub fn all_from_value(value: &serde_json::Value) -> Vec<Self> {
+        value["playEvents"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|event| event["isPitch"].as_bool() == Some(true))
+            .filter_map(|event| {
+                let Some(call) = PitchCall::from_details(&event["details"]) else {
+                    tracing::warn!(call = %event["details"]["call"], "Skipping pitch with an unknown call");
+                    return None;
+                };
+                let pitch_type = event["details"]["type"]["description"].as_str().map(str::to_string);
+
+                Some(Self { call, pitch_type })
+            })
+            .collect()
+    }
+}
+
+impl Preprocess for Pitch {
+    fn to_value(&self, _options: &PreprocessOptions) -> serde_json::Value {
+        serde_json::json!({ "call": self.call.name(), "pitch_type": self.pitch_type })
+    }
+}
+
+/// Whether a replay review let the call on the field stand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewOutcome {
+    Upheld,
+    Overturned,
+}
+
+/// The replay review of the call in a play, whether a manager challenged it or the umpires reviewed it themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Review {
+    pub outcome: ReviewOutcome,
+    /// The team that challenged the call, or `None` if the umpires reviewed it.
+    pub challenge_team_id: Option<u32>,
+}
+
+impl Review {
+    /// The finished review of the play, from its `reviewDetails` (or those of the last of its events that has them), if it was reviewed.
+    pub fn from_value(value: &serde_json::Value) -> Option<Self> {
+        let details = std::iter::once(&value["reviewDetails"])
+            .chain(value["playEvents"].as_array().into_iter().flatten().rev().map(|event| &event["reviewDetails"]))
+            .find(|details| details.is_object())?;
+        if details["inProgress"].as_bool() == Some(true) {
+            return None;
+        }
+
+        let outcome = if details["isOverturned"].as_bool()? { ReviewOutcome::Overturned } else { ReviewOutcome::Upheld };
+        let challenge_team_id = details["challengeTeamId"].as_u64().map(|id| id as u32);
+
+        Some(Self { outcome, challenge_team_id })
+    }
+}
+
+impl Preprocess for Review {
+    fn to_value(&self, _options: &PreprocessOptions) -> serde_json::Value {
+        let outcome = match self.outcome {
+            ReviewOutcome::Upheld => "Upheld",
+            ReviewOutcome::Overturned => "Overturned",
+        };
+
+        serde_json::json!({ "outcome": outcome, "challenge_team_id": self.challenge_team_id })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Inning {
+    pub number: u8,
+    pub top: bool,
+}
+
+impl Inning {
+    pub fn from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let number = value["inning"].as_u64().ok_or("No inning number")? as u8;
+        let top = value["isTopInning"].as_bool().ok_or("No inning half")?;
+
+        Ok(Self { number, top })
+    }
+}
+
+impl std::fmt::Display for Inning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.number, if self.top { "top" } else { "bottom" })
+    }
+}
+
+impl Preprocess for Inning {
+    fn to_value(&self, _options: &PreprocessOptions) -> serde_json::Value {
+        serde_json::json!({ "number": self.number, "top": self.top })
+    }
+}
+
+/// What a batted ball came to (see [`Play::outcome`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The bases the batter reached: 0 if they were out, up to 4 if they scored.
+    pub total_bases: u8,
+    /// The runs that scored, including the batter's.
+    pub runs: u8,
+}
+
+/// A runner who stole a base in the same play as another (e.g. the trailing runner of a double steal).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Steal {
+    pub runner: String,
+    pub base: u8,
+}
+
+/// The play types whose feed events name the base they happened at.
+const BASE_PLAY_TYPES: [&str; 6] = ["Pickoff", "Pickoff Error", "Pickoff Attempt", "Caught Stealing", "Pickoff Caught Stealing", "Stolen Base"];
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum Play {
+    // outs
+    Groundout {
+        inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
+        batter: String,
+        pitcher: String,
+        fielders: Vec<String>,
+        movements: Vec<Movement>,
+    },
+    BuntGroundout {
+        inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
+        batter: String,
+        pitcher: String,
+        fielders: Vec<String>,
+        movements: Vec<Movement>,
+    },
+    Strikeout {
+        inning: Inning,
+        description: Option<String>,
+        batter: String,
+        pitcher: String,
+        movements: Vec<Movement>,
+    },
     Lineout {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -453,6 +1393,9 @@ pub enum Play {
     },
     BuntLineout {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -460,6 +1403,9 @@ pub enum Play {
     },
     Flyout {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -467,6 +1413,9 @@ pub enum Play {
     },
     PopOut {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -474,6 +1423,9 @@ pub enum Play {
     },
     BuntPopOut {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -481,6 +1433,9 @@ pub enum Play {
     },
     Forceout {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -488,6 +1443,9 @@ pub enum Play {
     },
     FieldersChoiceOut {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -496,6 +1454,9 @@ pub enum Play {
     },
     DoublePlay {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -503,6 +1464,9 @@ pub enum Play {
     },
     TriplePlay {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -510,6 +1474,7 @@ pub enum Play {
     },
     RunnerDoublePlay {
         inning: Inning,
+        description: Option<String>,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -517,6 +1482,7 @@ pub enum Play {
     },
     RunnerTriplePlay {
         inning: Inning,
+        description: Option<String>,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -524,6 +1490,9 @@ pub enum Play {
     },
     GroundedIntoDoublePlay {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -531,6 +1500,7 @@ pub enum Play {
     },
     StrikeoutDoublePlay {
         inning: Inning,
+        description: Option<String>,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -538,6 +1508,7 @@ pub enum Play {
     },
     Pickoff {
         inning: Inning,
+        description: Option<String>,
         base: u8,
         runner: String,
         fielders: Vec<String>,
@@ -545,6 +1516,7 @@ pub enum Play {
     },
     PickoffError {
         inning: Inning,
+        description: Option<String>,
         base: u8,
         runner: String,
         fielders: Vec<String>,
@@ -552,6 +1524,7 @@ pub enum Play {
     },
     CaughtStealing {
         inning: Inning,
+        description: Option<String>,
         base: u8,
         runner: String,
         fielders: Vec<String>,
@@ -559,6 +1532,7 @@ pub enum Play {
     },
     PickoffCaughtStealing {
         inning: Inning,
+        description: Option<String>,
         base: u8,
         runner: String,
         fielders: Vec<String>,
@@ -566,41 +1540,49 @@ pub enum Play {
     },
     WildPitch {
         inning: Inning,
+        description: Option<String>,
         pitcher: String,
         runner: String,
         movements: Vec<Movement>,
     },
     RunnerOut {
         inning: Inning,
+        description: Option<String>,
         runner: String,
         fielders: Vec<String>,
         movements: Vec<Movement>,
     },
     FieldOut {
         inning: Inning,
+        description: Option<String>,
         fielder: String,
         runner: String,
         movements: Vec<Movement>,
     },
     BatterOut {
         inning: Inning,
+        description: Option<String>,
         batter: String,
-        catcher: String,
+        /// Missing if the feed does not credit the catcher.
+        catcher: Option<String>,
         movements: Vec<Movement>,
     },
     Balk {
         inning: Inning,
+        description: Option<String>,
         pitcher: String,
         movements: Vec<Movement>,
     },
     PassedBall {
         inning: Inning,
+        description: Option<String>,
         pitcher: String,
         catcher: String,
         movements: Vec<Movement>,
     },
     Error {
         inning: Inning,
+        description: Option<String>,
         pitcher: String,
         catcher: String,
         movements: Vec<Movement>,
@@ -608,48 +1590,66 @@ pub enum Play {
     // scores
     Single {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         movements: Vec<Movement>,
     },
     Double {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         movements: Vec<Movement>,
     },
     Triple {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         movements: Vec<Movement>,
     },
     HomeRun {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         movements: Vec<Movement>,
     },
     Walk {
         inning: Inning,
+        description: Option<String>,
         batter: String,
         pitcher: String,
         movements: Vec<Movement>,
     },
     IntentWalk {
         inning: Inning,
+        description: Option<String>,
         batter: String,
         pitcher: String,
         movements: Vec<Movement>,
     },
     HitByPitch {
         inning: Inning,
+        description: Option<String>,
         batter: String,
         pitcher: String,
         movements: Vec<Movement>,
     },
     FieldersChoice {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
@@ -657,94 +1657,248 @@ pub enum Play {
     },
     CatcherInterference {
         inning: Inning,
+        description: Option<String>,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
         movements: Vec<Movement>,
     },
+    /// The batter interfered with the catcher, and is out.
+    BatterInterference {
+        inning: Inning,
+        description: Option<String>,
+        batter: String,
+        pitcher: String,
+        movements: Vec<Movement>,
+    },
+    /// A fan interfered with a ball in play, and the umpires placed the runners.
+    FanInterference {
+        inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
+        batter: String,
+        pitcher: String,
+        movements: Vec<Movement>,
+    },
     StolenBase {
         inning: Inning,
+        description: Option<String>,
         base: u8,
         runner: String,
+        /// The other runners who stole a base in the play, in a double or triple steal.
+        #[serde(default)]
+        other_steals: Vec<Steal>,
         movements: Vec<Movement>,
     },
     // other
     SacFly {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
-        scoring_runner: String,
+        /// Missing if the feed does not list the runner.
+        scoring_runner: Option<String>,
         movements: Vec<Movement>,
     },
     SacFlyDoublePlay {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
-        scoring_runner: String,
+        /// Missing if the feed does not list the runner.
+        scoring_runner: Option<String>,
         movements: Vec<Movement>,
     },
     SacBunt {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
-        runner: String,
+        /// Missing if the feed does not list the runner.
+        runner: Option<String>,
         movements: Vec<Movement>,
     },
     SacBuntDoublePlay {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
-        runner: String,
+        /// Missing if the feed does not list the runner.
+        runner: Option<String>,
         movements: Vec<Movement>,
     },
     FieldError {
         inning: Inning,
+        description: Option<String>,
+        #[serde(default)]
+        hit_data: HitData,
         batter: String,
         pitcher: String,
         fielders: Vec<String>,
         movements: Vec<Movement>,
     },
+    DefensiveIndifference {
+        inning: Inning,
+        description: Option<String>,
+        runner: String,
+        movements: Vec<Movement>,
+    },
+    PickoffAttempt {
+        inning: Inning,
+        description: Option<String>,
+        base: u8,
+        pitcher: String,
+        runner: String,
+        movements: Vec<Movement>,
+    },
+    PitchingChange {
+        inning: Inning,
+        description: Option<String>,
+        new_pitcher: String,
+    },
+    MoundVisit {
+        inning: Inning,
+        description: Option<String>,
+    },
+    /// A note about the game (e.g. a rain delay starting or ending), which is its description.
     GameAdvisory {
         inning: Inning,
+        description: Option<String>,
+    },
+    /// The runner placed on second base at the start of an extra half-inning.
+    AutomaticRunner {
+        inning: Inning,
+        description: Option<String>,
+        runner: String,
     },
     Ejection {
         inning: Inning,
+        description: Option<String>,
+        /// Who was ejected, by name if the feed identifies them, and otherwise as the description names them
+        /// (e.g. `Manager Aaron Boone`). `None` for ejections saved before it was parsed, or without a description.
+        #[serde(default)]
+        ejected: Option<String>,
+        /// Why they were ejected, if the description says (e.g. `arguing balls and strikes`).
+        #[serde(default)]
+        reason: Option<String>,
         movements: Vec<Movement>,
     }
 }
 
+/// The fields shared by most plays.
+struct CommonFields {
+    inning: Inning,
+    batter: Result<String, String>,
+    pitcher: Result<String, String>,
+    fielders: Vec<String>,
+    movements: Vec<Movement>,
+}
+
 impl Play {
-    // outs
-    async fn groundout_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
+    fn batter_from_value(value: &serde_json::Value) -> Result<String, String> {
+        match value["matchup"]["batter"]["fullName"].as_str() {
+            Some(batter) => Ok(batter.to_string()),
+            None => Err("No batter".to_string()),
         }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+    }
 
-        Ok(Play::Groundout {
+    fn pitcher_from_value(value: &serde_json::Value) -> Result<String, String> {
+        match value["matchup"]["pitcher"]["fullName"].as_str() {
+            Some(pitcher) => Ok(pitcher.to_string()),
+            None => Err("No pitcher".to_string()),
+        }
+    }
+
+    fn description_from_value(value: &serde_json::Value) -> Option<String> {
+        value["result"]["description"].as_str().map(str::to_string)
+    }
+
+    /// The name of the `index`th runner in the play.
+    fn runner_from_value(value: &serde_json::Value, index: usize) -> Result<String, String> {
+        match value["runners"][index]["details"]["runner"]["fullName"].as_str() {
+            Some(runner) => Ok(runner.to_string()),
+            None => Err("No runner".to_string()),
+        }
+    }
+
+    /// The names of the fielders credited in the play, in credit order (every credit of each runner in turn, e.g. all
+    /// three fielders of a 6-4-3 double play), each only the first time they are credited. Looked up by id concurrently.
+    async fn fielders_from_value(value: &serde_json::Value) -> Result<Vec<String>, String> {
+        let fielder_ids = Play::fielder_credits_from_value(value)?.into_iter().map(|(id, _)| id);
+
+        futures::future::try_join_all(fielder_ids.map(get_player_name_from_id)).await
+    }
+
+    /// The scorekeeping position numbers (1 for the pitcher to 9 for the right fielder) the fielders of the play
+    /// were credited at, in the same order as [`Play::fielders_from_value`]. `None` for a credit without a fielding position.
+    pub fn fielder_positions_from_value(value: &serde_json::Value) -> Vec<Option<u8>> {
+        Play::fielder_credits_from_value(value).unwrap_or_default().into_iter().map(|(_, position)| position).collect()
+    }
+
+    /// The id of each fielder credited in the play and the position they were first credited at, in credit order.
+    fn fielder_credits_from_value(value: &serde_json::Value) -> Result<Vec<(usize, Option<u8>)>, String> {
+        let mut fielders = Vec::<(usize, Option<u8>)>::new();
+        for runner in value["runners"].as_array().ok_or("No runners")? {
+            for credit in runner["credits"].as_array().into_iter().flatten() {
+                if let Some(id) = credit["player"]["id"].as_u64() {
+                    if !fielders.iter().any(|(fielder_id, _)| *fielder_id == id as usize) {
+                        let position = credit["position"]["code"].as_str().and_then(|code| code.parse().ok()).filter(|position| (1..=9).contains(position));
+                        fielders.push((id as usize, position));
+                    }
+                }
+            }
+        }
+
+        Ok(fielders)
+    }
+
+    /// The movements of every runner in the play.
+    fn movements_from_value(value: &serde_json::Value) -> Result<Vec<Movement>, String> {
+        value["runners"].as_array().ok_or("No runners")?.iter().filter_map(|runner| {
+            // some error and advisory movements have no named runner; they are skipped rather than failing the play
+            let Some(name) = runner["details"]["runner"]["fullName"].as_str() else {
+                tracing::warn!(runner = %runner["details"]["runner"], "Skipping movement without a runner name");
+                return None;
+            };
+
+            Some(Movement::from_runner_and_value(name.to_string(), &runner["movement"]))
+        }).collect()
+    }
+
+    /// The movement of only the first runner in the play.
+    fn first_movement_from_value(value: &serde_json::Value) -> Result<Vec<Movement>, String> {
+        Ok(vec![Movement::from_runner_and_value(
+            Play::runner_from_value(value, 0)?,
+            &value["runners"][0]["movement"],
+        )?])
+    }
+
+    /// Parse the fields shared by most plays. Fielders are only looked up if `with_fielders` is set, since each one is a request.
+    async fn common_fields(value: &serde_json::Value, with_fielders: bool) -> Result<CommonFields, String> {
+        let inning = Inning::from_value(&value["about"])?;
+        let batter = Play::batter_from_value(value);
+        let pitcher = Play::pitcher_from_value(value);
+        let fielders = if with_fielders {
+            Play::fielders_from_value(value).await?
+        } else {
+            Vec::new()
+        };
+        let movements = Play::movements_from_value(value)?;
+
+        Ok(CommonFields {
             inning,
             batter,
             pitcher,
@@ -753,300 +1907,141 @@ impl Play {
         })
     }
 
+    // outs
+    async fn groundout_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
+
+        Ok(Play::Groundout {
+            inning,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
+            fielders,
+            movements,
+        })
+    }
+
     async fn bunt_groundout_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::BuntGroundout {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn strikeout_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::Strikeout {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn lineout_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::Lineout {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn bunt_lineout_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::BuntLineout {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn flyout_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::Flyout {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn pop_out_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::PopOut {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn bunt_pop_out_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::BuntPopOut {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn forceout_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::Forceout {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn fielders_choice_out_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let scoring_runner = match value["runners"][1]["details"]["runner"]["fullName"].as_str() {
-            Some(runner) => runner.to_string(),
-            None => return Err("No scoring runner".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
+        let scoring_runner = Play::runner_from_value(value, 1)?;
 
         Ok(Play::FieldersChoiceOut {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             scoring_runner,
             movements,
@@ -1054,230 +2049,93 @@ impl Play {
     }
 
     async fn double_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::DoublePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn triple_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::TriplePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn runner_double_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::RunnerDoublePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn runner_triple_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
-        Ok(Play::TriplePlay {
+        Ok(Play::RunnerTriplePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn grounded_into_double_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::GroundedIntoDoublePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn strikeout_double_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::StrikeoutDoublePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn pickoff_from_value_and_base(value: &serde_json::Value, base: u8) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, fielders, movements, .. } = Play::common_fields(value, true).await?;
+        let runner = Play::runner_from_value(value, 0)?;
 
         Ok(Play::Pickoff {
             inning,
+            description: Play::description_from_value(value),
             base,
             runner,
             fielders,
@@ -1286,26 +2144,12 @@ impl Play {
     }
 
     async fn pickoff_error_from_value_and_base(value: &serde_json::Value, base: u8) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, fielders, movements, .. } = Play::common_fields(value, true).await?;
+        let runner = Play::runner_from_value(value, 0)?;
 
         Ok(Play::PickoffError {
             inning,
+            description: Play::description_from_value(value),
             base,
             runner,
             fielders,
@@ -1314,26 +2158,12 @@ impl Play {
     }
 
     async fn caught_stealing_from_value_and_base(value: &serde_json::Value, base: u8) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, fielders, movements, .. } = Play::common_fields(value, true).await?;
+        let runner = Play::runner_from_value(value, 0)?;
 
         Ok(Play::CaughtStealing {
             inning,
+            description: Play::description_from_value(value),
             base,
             runner,
             fielders,
@@ -1342,26 +2172,12 @@ impl Play {
     }
 
     async fn pickoff_caught_stealing_from_value_and_base(value: &serde_json::Value, base: u8) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, fielders, movements, .. } = Play::common_fields(value, true).await?;
+        let runner = Play::runner_from_value(value, 0)?;
 
         Ok(Play::PickoffCaughtStealing {
             inning,
+            description: Play::description_from_value(value),
             base,
             runner,
             fielders,
@@ -1370,19 +2186,14 @@ impl Play {
     }
 
     async fn wild_pitch_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let movements = vec![Movement::from_runner_and_value(
-            runner.clone(),
-            &value["runners"][0]["movement"],
-        )];
+        let inning = Inning::from_value(&value["about"])?;
+        let pitcher = Play::pitcher_from_value(value)?;
+        let runner = Play::runner_from_value(value, 0)?;
+        let movements = Play::first_movement_from_value(value)?;
 
         Ok(Play::WildPitch {
             inning,
+            description: Play::description_from_value(value),
             pitcher,
             runner,
             movements,
@@ -1390,26 +2201,12 @@ impl Play {
     }
 
     async fn runner_out_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, fielders, movements, .. } = Play::common_fields(value, true).await?;
+        let runner = Play::runner_from_value(value, 0)?;
 
         Ok(Play::RunnerOut {
             inning,
+            description: Play::description_from_value(value),
             runner,
             fielders,
             movements,
@@ -1417,19 +2214,17 @@ impl Play {
     }
 
     async fn field_out_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
+        let inning = Inning::from_value(&value["about"])?;
         let fielder = match value["runners"][0]["details"]["fielder"]["fullName"].as_str() {
             Some(fielder) => fielder.to_string(),
             None => return Err("No fielder".to_string()),
         };
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let movements = vec![Movement::from_runner_and_value(
-            runner.clone(),
-            &value["runners"][0]["movement"],
-        )];
+        let runner = Play::runner_from_value(value, 0)?;
+        let movements = Play::first_movement_from_value(value)?;
 
         Ok(Play::FieldOut {
             inning,
+            description: Play::description_from_value(value),
             fielder,
             runner,
             movements,
@@ -1437,20 +2232,16 @@ impl Play {
     }
 
     async fn batter_out_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
+        let CommonFields { inning, batter, movements, .. } = Play::common_fields(value, false).await?;
+        let batter = batter?;
+        let catcher = match value["runners"][0]["credits"][0]["player"]["id"].as_u64() {
+            Some(catcher_id) => Some(get_player_name_from_id(catcher_id as usize).await?),
+            None => None,
         };
-        let catcher_id = value["runners"][0]["credits"][0]["player"]["id"].as_u64().unwrap() as usize;
-        let catcher = get_player_name_from_id(catcher_id).await?;
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
 
         Ok(Play::BatterOut {
             inning,
+            description: Play::description_from_value(value),
             batter,
             catcher,
             movements,
@@ -1458,26 +2249,20 @@ impl Play {
     }
 
     async fn balk_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let movements = vec![Movement::from_runner_and_value(
-            runner.clone(),
-            &value["runners"][0]["movement"],
-        )];
+        let inning = Inning::from_value(&value["about"])?;
+        let pitcher = Play::pitcher_from_value(value)?;
+        let movements = Play::first_movement_from_value(value)?;
 
         Ok(Play::Balk {
             inning,
+            description: Play::description_from_value(value),
             pitcher,
             movements,
         })
     }
 
     async fn passed_ball_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
+        let inning = Inning::from_value(&value["about"])?;
         let pitcher = match value["runners"][0]["details"]["pitcher"]["fullName"].as_str() {
             Some(pitcher) => pitcher.to_string(),
             None => return Err("No pitcher".to_string()),
@@ -1486,14 +2271,11 @@ impl Play {
             Some(catcher) => catcher.to_string(),
             None => return Err("No catcher".to_string()),
         };
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let movements = vec![Movement::from_runner_and_value(
-            runner.clone(),
-            &value["runners"][0]["movement"],
-        )];
+        let movements = Play::first_movement_from_value(value)?;
 
         Ok(Play::PassedBall {
             inning,
+            description: Play::description_from_value(value),
             pitcher,
             catcher,
             movements,
@@ -1501,22 +2283,17 @@ impl Play {
     }
 
     async fn error_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
+        let inning = Inning::from_value(&value["about"])?;
+        let pitcher = Play::pitcher_from_value(value)?;
         let catcher = match value["matchup"]["catcher"]["fullName"].as_str() {
             Some(catcher) => catcher.to_string(),
             None => return Err("No catcher".to_string()),
         };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let movements = Play::movements_from_value(value)?;
 
         Ok(Play::Error {
             inning,
+            description: Play::description_from_value(value),
             pitcher,
             catcher,
             movements,
@@ -1525,282 +2302,191 @@ impl Play {
 
     // scores
     async fn single_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::Single {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn double_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::Double {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn triple_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::Triple {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn home_run_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::HomeRun {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn walk_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::Walk {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn intent_walk_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::IntentWalk {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn hit_by_pitch_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
 
         Ok(Play::HitByPitch {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             movements,
         })
     }
 
     async fn fielders_choice_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::FieldersChoice {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
     async fn catcher_interference_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::CatcherInterference {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
+    async fn batter_interference_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
+
+        Ok(Play::BatterInterference {
+            inning,
+            description: Play::description_from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
+            movements,
+        })
+    }
+
+    async fn fan_interference_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let CommonFields { inning, batter, pitcher, movements, .. } = Play::common_fields(value, false).await?;
+
+        Ok(Play::FanInterference {
+            inning,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
+            movements,
+        })
+    }
+
     async fn stolen_base_from_value_and_base(value: &serde_json::Value, base: u8) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let runner = value["runners"][0]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let inning = Inning::from_value(&value["about"])?;
+        let runner = Play::runner_from_value(value, 0)?;
+        let other_steals = Play::other_steals_from_value(value, &runner);
+        let movements = Play::movements_from_value(value)?;
 
         Ok(Play::StolenBase {
             inning,
+            description: Play::description_from_value(value),
             base,
             runner,
+            other_steals,
             movements,
         })
     }
 
-    // other
-    async fn sac_fly_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
+    /// The steals of the runners other than `runner` in a stolen base play, each at the base its event names,
+    /// or else the base the runner ended up on.
+    fn other_steals_from_value(value: &serde_json::Value, runner: &str) -> Vec<Steal> {
+        let mut other_steals = Vec::<Steal>::new();
+        for runner_value in value["runners"].as_array().into_iter().flatten() {
+            let Some(name) = runner_value["details"]["runner"]["fullName"].as_str() else { continue };
+            let (play_type, base) = Play::play_type_from_event(runner_value["details"]["event"].as_str().unwrap_or_default());
+            // the same runner can have more than one movement, e.g. when they also advance on a throwing error
+            if play_type != "Stolen Base" || name == runner || other_steals.iter().any(|steal| steal.runner == name) {
+                continue;
+            }
+            let Some(base) = base.or_else(|| base_value_to_option_u8(&runner_value["movement"]["end"]).ok().flatten()) else { continue };
+
+            other_steals.push(Steal { runner: name.to_string(), base });
         }
-        let scoring_runner = value["runners"][1]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
 
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        other_steals
+    }
+
+    // other
+    async fn sac_fly_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
+        let scoring_runner = Play::runner_from_value(value, 1).ok();
 
         Ok(Play::SacFly {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             scoring_runner,
             movements,
@@ -1808,36 +2494,15 @@ impl Play {
     }
 
     async fn sac_fly_double_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let scoring_runner = value["runners"][1]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
+        let scoring_runner = Play::runner_from_value(value, 1).ok();
 
         Ok(Play::SacFlyDoublePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             scoring_runner,
             movements,
@@ -1845,36 +2510,15 @@ impl Play {
     }
 
     async fn sac_bunt_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let runner = value["runners"][1]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
+        let runner = Play::runner_from_value(value, 1).ok();
 
         Ok(Play::SacBunt {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             runner,
             movements,
@@ -1882,36 +2526,15 @@ impl Play {
     }
 
     async fn sac_bunt_double_play_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-        let runner = value["runners"][1]["details"]["runner"]["fullName"].as_str().unwrap().to_string();
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
+        let runner = Play::runner_from_value(value, 1).ok();
 
         Ok(Play::SacBuntDoublePlay {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             runner,
             movements,
@@ -1919,63 +2542,163 @@ impl Play {
     }
 
     async fn field_error_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let batter = match value["matchup"]["batter"]["fullName"].as_str() {
-            Some(batter) => batter.to_string(),
-            None => return Err("No batter".to_string()),
-        };
-        let pitcher = match value["matchup"]["pitcher"]["fullName"].as_str() {
-            Some(pitcher) => pitcher.to_string(),
-            None => return Err("No pitcher".to_string()),
-        };
-        let fielder_ids = value["runners"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .filter_map(|runner| runner["credits"][0]["player"]["id"].as_u64())
-            .map(|id| id as usize);
-        let mut fielders = Vec::new();
-        for id in fielder_ids {
-            fielders.push(get_player_name_from_id(id).await?);
-        }
-
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let CommonFields { inning, batter, pitcher, fielders, movements } = Play::common_fields(value, true).await?;
 
         Ok(Play::FieldError {
             inning,
-            batter,
-            pitcher,
+            description: Play::description_from_value(value),
+            hit_data: HitData::from_value(value),
+            batter: batter?,
+            pitcher: pitcher?,
             fielders,
             movements,
         })
     }
 
+    async fn defensive_indifference_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let inning = Inning::from_value(&value["about"])?;
+        let runner = Play::runner_from_value(value, 0)?;
+        let movements = Play::movements_from_value(value)?;
+
+        Ok(Play::DefensiveIndifference {
+            inning,
+            description: Play::description_from_value(value),
+            runner,
+            movements,
+        })
+    }
+
+    async fn pickoff_attempt_from_value_and_base(value: &serde_json::Value, base: u8) -> Result<Self, String> {
+        let inning = Inning::from_value(&value["about"])?;
+        let pitcher = Play::pitcher_from_value(value)?;
+        let runner = Play::runner_from_value(value, 0)?;
+        let movements = Play::movements_from_value(value)?;
+
+        Ok(Play::PickoffAttempt {
+            inning,
+            description: Play::description_from_value(value),
+            base,
+            pitcher,
+            runner,
+            movements,
+        })
+    }
+
     async fn game_advistory_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
+        let inning = Inning::from_value(&value["about"])?;
 
         Ok(Play::GameAdvisory {
             inning,
+            description: Play::description_from_value(value),
+        })
+    }
+
+    /// A pitching change given as a play of its own rather than as an event during one, whose pitcher is the new pitcher.
+    async fn pitching_change_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let inning = Inning::from_value(&value["about"])?;
+        let new_pitcher = Play::pitcher_from_value(value)?;
+
+        Ok(Play::PitchingChange {
+            inning,
+            description: Play::description_from_value(value),
+            new_pitcher,
+        })
+    }
+
+    /// A mound visit given as a play of its own rather than as an event during one.
+    async fn mound_visit_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let inning = Inning::from_value(&value["about"])?;
+
+        Ok(Play::MoundVisit {
+            inning,
+            description: Play::description_from_value(value),
+        })
+    }
+
+    /// An automatic runner given as a play of its own rather than inferred from the half-inning's first plays.
+    async fn automatic_runner_from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let inning = Inning::from_value(&value["about"])?;
+        let runner = Play::runner_from_value(value, 0)?;
+
+        Ok(Play::AutomaticRunner {
+            inning,
+            description: Play::description_from_value(value),
+            runner,
         })
     }
 
     async fn ejection_from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let inning = Inning::from_value(&value["about"]);
-        let movements = value["runners"].as_array().unwrap().iter().map(|runner| Movement::from_runner_and_value(
-            runner["details"]["runner"]["fullName"].as_str().unwrap().to_string(),
-            &runner["movement"],
-        )).collect();
+        let inning = Inning::from_value(&value["about"])?;
+        let movements = Play::movements_from_value(value)?;
+        let description = Play::description_from_value(value);
+
+        // the description reads e.g. "Manager Aaron Boone ejected by HP umpire Angel Hernandez for arguing balls and strikes."
+        let (subject, umpire) = description.as_deref().and_then(|description| description.split_once(" ejected by ")).unzip();
+        let reason = umpire.and_then(|umpire| umpire.split_once(" for ")).map(|(_, reason)| reason.trim_end_matches('.').to_string());
+        // the ejection event names who was ejected by id, which is looked up rather than guessed from the description
+        let ejected_id = value["playEvents"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|event| event["details"]["eventType"] == "ejection")
+            .and_then(|event| event["player"]["id"].as_u64());
+        let ejected = match ejected_id {
+            Some(id) => get_player_name_from_id(id as usize).await.ok(),
+            None => None,
+        };
+        let ejected = ejected.or(subject.map(str::to_string));
 
         Ok(Play::Ejection {
             inning,
+            description,
+            ejected,
+            reason,
             movements,
         })
     }
 
+    /// The play type (as returned by [`Play::play_type`]) of a feed event, and the base the event names, if any.
+    /// Feed events name the base of pickoffs and steals (`"Pickoff 1B"`), and abbreviate some types (`"Grounded Into DP"`).
+    fn play_type_from_event(event: &str) -> (&str, Option<u8>) {
+        let (play_type, base) = match event.rsplit_once(' ') {
+            Some((play_type, base)) if BASE_PLAY_TYPES.contains(&play_type) => match base {
+                "1B" => (play_type, Some(1)),
+                "2B" => (play_type, Some(2)),
+                "3B" => (play_type, Some(3)),
+                "Home" => (play_type, Some(4)),
+                _ => (event, None),
+            },
+            _ => (event, None),
+        };
+
+        let play_type = match play_type {
+            "Grounded Into DP" => "Grounded Into Double Play",
+            "Defensive Indiff" => "Defensive Indifference",
+            play_type => play_type,
+        };
+
+        (play_type, base)
+    }
+
+    /// The base of a pickoff or steal whose event does not name it, from the first runner's movement:
+    /// the base they were on for a pickoff, otherwise the base they were stealing.
+    fn base_from_movement(value: &serde_json::Value, stealing: bool) -> Result<u8, String> {
+        let movement = &value["runners"][0]["movement"];
+        let base = if !stealing {
+            &movement["start"]
+        } else if movement["isOut"].as_bool().unwrap_or(false) {
+            &movement["outBase"]
+        } else {
+            &movement["end"]
+        };
+
+        base_value_to_option_u8(base)?.ok_or_else(|| "No base".to_string())
+    }
+
     pub async fn from_value(value: &serde_json::Value) -> Result<Self, String> {
-        let play_type = value["result"]["event"].as_str().unwrap();
+        let event = value["result"]["event"].as_str().ok_or("No play type")?;
+        let (play_type, base) = Play::play_type_from_event(event);
+        let base = |stealing| base.map_or_else(|| Play::base_from_movement(value, stealing), Ok);
 
         match play_type {
             "Groundout" => Play::groundout_from_value(value).await,
@@ -1989,25 +2712,18 @@ impl Play {
             "Forceout" => Play::forceout_from_value(value).await,
             "Fielders Choice Out" => Play::fielders_choice_out_from_value(value).await,
             "Catcher Interference" => Play::catcher_interference_from_value(value).await,
+            "Batter Interference" => Play::batter_interference_from_value(value).await,
+            "Fan Interference" => Play::fan_interference_from_value(value).await,
             "Double Play" => Play::double_play_from_value(value).await,
             "Triple Play" => Play::triple_play_from_value(value).await,
             "Runner Double Play" => Play::runner_double_play_from_value(value).await,
             "Runner Triple Play" => Play::runner_triple_play_from_value(value).await,
-            "Grounded Into DP" => Play::grounded_into_double_play_from_value(value).await,
+            "Grounded Into Double Play" => Play::grounded_into_double_play_from_value(value).await,
             "Strikeout Double Play" => Play::strikeout_double_play_from_value(value).await,
-            "Pickoff 1B" => Play::pickoff_from_value_and_base(value, 1).await,
-            "Pickoff 2B" => Play::pickoff_from_value_and_base(value, 2).await,
-            "Pickoff 3B" => Play::pickoff_from_value_and_base(value, 3).await,
-            "Pickoff Error 1B" => Play::pickoff_error_from_value_and_base(value, 1).await,
-            "Pickoff Error 2B" => Play::pickoff_error_from_value_and_base(value, 2).await,
-            "Pickoff Error 3B" => Play::pickoff_error_from_value_and_base(value, 3).await,
-            "Caught Stealing 2B" => Play::caught_stealing_from_value_and_base(value, 2).await,
-            "Caught Stealing 3B" => Play::caught_stealing_from_value_and_base(value, 3).await,
-            "Caught Stealing Home" => Play::caught_stealing_from_value_and_base(value, 4).await,
-            "Pickoff Caught Stealing 1B" => Play::pickoff_caught_stealing_from_value_and_base(value, 1).await,
-            "Pickoff Caught Stealing 2B" => Play::pickoff_caught_stealing_from_value_and_base(value, 2).await,
-            "Pickoff Caught Stealing 3B" => Play::pickoff_caught_stealing_from_value_and_base(value, 3).await,
-            "Pickoff Caught Stealing Home" => Play::pickoff_caught_stealing_from_value_and_base(value, 4).await,
+            "Pickoff" => Play::pickoff_from_value_and_base(value, base(false)?).await,
+            "Pickoff Error" => Play::pickoff_error_from_value_and_base(value, base(false)?).await,
+            "Caught Stealing" => Play::caught_stealing_from_value_and_base(value, base(true)?).await,
+            "Pickoff Caught Stealing" => Play::pickoff_caught_stealing_from_value_and_base(value, base(true)?).await,
             "Wild Pitch" => Play::wild_pitch_from_value(value).await,
             "Runner Out" => Play::runner_out_from_value(value).await,
             "Field Out" => Play::field_out_from_value(value).await,
@@ -2023,578 +2739,1315 @@ impl Play {
             "Intent Walk" => Play::intent_walk_from_value(value).await,
             "Hit By Pitch" => Play::hit_by_pitch_from_value(value).await,
             "Fielders Choice" => Play::fielders_choice_from_value(value).await,
-            "Stolen Base 1B" => Play::stolen_base_from_value_and_base(value, 1).await,
-            "Stolen Base 2B" => Play::stolen_base_from_value_and_base(value, 2).await,
-            "Stolen Base 3B" => Play::stolen_base_from_value_and_base(value, 3).await,
-            "Stolen Base Home" => Play::stolen_base_from_value_and_base(value, 4).await,
+            "Stolen Base" => Play::stolen_base_from_value_and_base(value, base(true)?).await,
             "Sac Fly" => Play::sac_fly_from_value(value).await,
             "Sac Fly Double Play" => Play::sac_fly_double_play_from_value(value).await,
             "Sac Bunt" => Play::sac_bunt_from_value(value).await,
             "Sac Bunt Double Play" => Play::sac_bunt_double_play_from_value(value).await,
             "Field Error" => Play::field_error_from_value(value).await,
+            "Defensive Indifference" => Play::defensive_indifference_from_value(value).await,
+            "Pickoff Attempt" => Play::pickoff_attempt_from_value_and_base(value, base(false)?).await,
+            "Pitching Change" => Play::pitching_change_from_value(value).await,
+            "Mound Visit" => Play::mound_visit_from_value(value).await,
             "Game Advisory" => Play::game_advistory_from_value(value).await,
+            "Automatic Runner" => Play::automatic_runner_from_value(value).await,
             "Ejection" => Play::ejection_from_value(value).await,
-            _ => panic!("Unknown play type: {}", play_type),
+            _ => Err(format!("Unknown play type: {}", event)),
         }
     }
-}
 
-impl Preprocess for Play {
-    fn preprocess(&self) -> String {
+    pub fn inning(&self) -> &Inning {
         match self {
-            Play::Groundout { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Groundout\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::BuntGroundout { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Bunt Groundout\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Strikeout { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Strikeout\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Lineout { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Lineout\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::BuntLineout { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Bunt Lineout\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Flyout { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Flyout\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::PopOut { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Pop Out\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::BuntPopOut { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Bunt Pop Out\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Forceout { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Forceout\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::FieldersChoiceOut { inning, batter, pitcher, fielders, scoring_runner, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Fielders Choice Out\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}], \"scoring_runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    scoring_runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::DoublePlay { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Double Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::TriplePlay { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Triple Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::RunnerDoublePlay { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Runner Double Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::RunnerTriplePlay { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Runner Triple Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::GroundedIntoDoublePlay { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Grounded Into Double Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::StrikeoutDoublePlay { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Strikeout Double Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Pickoff { inning, base, runner, fielders, movements } => {
-                let mut base_string = "home".to_string();
-                if *base != 4 {
-                    base_string = base.to_string();
-                }
+            Play::Groundout { inning, .. } |
+            Play::BuntGroundout { inning, .. } |
+            Play::Strikeout { inning, .. } |
+            Play::Lineout { inning, .. } |
+            Play::BuntLineout { inning, .. } |
+            Play::Flyout { inning, .. } |
+            Play::PopOut { inning, .. } |
+            Play::BuntPopOut { inning, .. } |
+            Play::Forceout { inning, .. } |
+            Play::FieldersChoiceOut { inning, .. } |
+            Play::DoublePlay { inning, .. } |
+            Play::TriplePlay { inning, .. } |
+            Play::RunnerDoublePlay { inning, .. } |
+            Play::RunnerTriplePlay { inning, .. } |
+            Play::GroundedIntoDoublePlay { inning, .. } |
+            Play::StrikeoutDoublePlay { inning, .. } |
+            Play::Pickoff { inning, .. } |
+            Play::PickoffError { inning, .. } |
+            Play::CaughtStealing { inning, .. } |
+            Play::PickoffCaughtStealing { inning, .. } |
+            Play::WildPitch { inning, .. } |
+            Play::RunnerOut { inning, .. } |
+            Play::FieldOut { inning, .. } |
+            Play::BatterOut { inning, .. } |
+            Play::Balk { inning, .. } |
+            Play::PassedBall { inning, .. } |
+            Play::Error { inning, .. } |
+            Play::Single { inning, .. } |
+            Play::Double { inning, .. } |
+            Play::Triple { inning, .. } |
+            Play::HomeRun { inning, .. } |
+            Play::Walk { inning, .. } |
+            Play::IntentWalk { inning, .. } |
+            Play::HitByPitch { inning, .. } |
+            Play::FieldersChoice { inning, .. } |
+            Play::CatcherInterference { inning, .. } |
+            Play::BatterInterference { inning, .. } |
+            Play::FanInterference { inning, .. } |
+            Play::StolenBase { inning, .. } |
+            Play::SacFly { inning, .. } |
+            Play::SacFlyDoublePlay { inning, .. } |
+            Play::SacBunt { inning, .. } |
+            Play::SacBuntDoublePlay { inning, .. } |
+            Play::FieldError { inning, .. } |
+            Play::DefensiveIndifference { inning, .. } |
+            Play::PickoffAttempt { inning, .. } |
+            Play::PitchingChange { inning, .. } |
+            Play::MoundVisit { inning, .. } |
+            Play::GameAdvisory { inning, .. } |
+            Play::AutomaticRunner { inning, .. } |
+            Play::Ejection { inning, .. } => inning,
+        }
+    }
 
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Pickoff\" }}\n{{ \"base\": \"{}\", \"runner\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    base_string,
-                    runner,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::PickoffError { inning, base, runner, fielders, movements } => {
-                let mut base_string = "home".to_string();
-                if *base != 4 {
-                    base_string = base.to_string();
-                }
+    /// The play's type, as it appears in the preprocessed output (e.g. `"Home Run"`).
+    pub fn play_type(&self) -> &'static str {
+        match self {
+            Play::Groundout { .. } => "Groundout",
+            Play::BuntGroundout { .. } => "Bunt Groundout",
+            Play::Strikeout { .. } => "Strikeout",
+            Play::Lineout { .. } => "Lineout",
+            Play::BuntLineout { .. } => "Bunt Lineout",
+            Play::Flyout { .. } => "Flyout",
+            Play::PopOut { .. } => "Pop Out",
+            Play::BuntPopOut { .. } => "Bunt Pop Out",
+            Play::Forceout { .. } => "Forceout",
+            Play::FieldersChoiceOut { .. } => "Fielders Choice Out",
+            Play::DoublePlay { .. } => "Double Play",
+            Play::TriplePlay { .. } => "Triple Play",
+            Play::RunnerDoublePlay { .. } => "Runner Double Play",
+            Play::RunnerTriplePlay { .. } => "Runner Triple Play",
+            Play::GroundedIntoDoublePlay { .. } => "Grounded Into Double Play",
+            Play::StrikeoutDoublePlay { .. } => "Strikeout Double Play",
+            Play::Pickoff { .. } => "Pickoff",
+            Play::PickoffError { .. } => "Pickoff Error",
+            Play::CaughtStealing { .. } => "Caught Stealing",
+            Play::PickoffCaughtStealing { .. } => "Pickoff Caught Stealing",
+            Play::WildPitch { .. } => "Wild Pitch",
+            Play::RunnerOut { .. } => "Runner Out",
+            Play::FieldOut { .. } => "Field Out",
+            Play::BatterOut { .. } => "Batter Out",
+            Play::Balk { .. } => "Balk",
+            Play::PassedBall { .. } => "Passed Ball",
+            Play::Error { .. } => "Error",
+            Play::Single { .. } => "Single",
+            Play::Double { .. } => "Double",
+            Play::Triple { .. } => "Triple",
+            Play::HomeRun { .. } => "Home Run",
+            Play::Walk { .. } => "Walk",
+            Play::IntentWalk { .. } => "Intent Walk",
+            Play::HitByPitch { .. } => "Hit By Pitch",
+            Play::FieldersChoice { .. } => "Fielders Choice",
+            Play::CatcherInterference { .. } => "Catcher Interference",
+            Play::BatterInterference { .. } => "Batter Interference",
+            Play::FanInterference { .. } => "Fan Interference",
+            Play::StolenBase { .. } => "Stolen Base",
+            Play::SacFly { .. } => "Sac Fly",
+            Play::SacFlyDoublePlay { .. } => "Sac Fly Double Play",
+            Play::SacBunt { .. } => "Sac Bunt",
+            Play::SacBuntDoublePlay { .. } => "Sac Bunt Double Play",
+            Play::FieldError { .. } => "Field Error",
+            Play::DefensiveIndifference { .. } => "Defensive Indifference",
+            Play::PickoffAttempt { .. } => "Pickoff Attempt",
+            Play::PitchingChange { .. } => "Pitching Change",
+            Play::MoundVisit { .. } => "Mound Visit",
+            Play::GameAdvisory { .. } => "Game Advisory",
+            Play::AutomaticRunner { .. } => "Automatic Runner",
+            Play::Ejection { .. } => "Ejection",
+        }
+    }
 
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Pickoff Error\" }}\n{{ \"base\": \"{}\", \"runner\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    base_string,
-                    runner,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::CaughtStealing { inning, base, runner, fielders, movements } => {
-                let mut base_string = "home".to_string();
-                if *base != 4 {
-                    base_string = base.to_string();
-                }
+    /// The official natural-language summary of the play, if the feed had one.
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Play::Groundout { description, .. } |
+            Play::BuntGroundout { description, .. } |
+            Play::Strikeout { description, .. } |
+            Play::Lineout { description, .. } |
+            Play::BuntLineout { description, .. } |
+            Play::Flyout { description, .. } |
+            Play::PopOut { description, .. } |
+            Play::BuntPopOut { description, .. } |
+            Play::Forceout { description, .. } |
+            Play::FieldersChoiceOut { description, .. } |
+            Play::DoublePlay { description, .. } |
+            Play::TriplePlay { description, .. } |
+            Play::RunnerDoublePlay { description, .. } |
+            Play::RunnerTriplePlay { description, .. } |
+            Play::GroundedIntoDoublePlay { description, .. } |
+            Play::StrikeoutDoublePlay { description, .. } |
+            Play::Pickoff { description, .. } |
+            Play::PickoffError { description, .. } |
+            Play::CaughtStealing { description, .. } |
+            Play::PickoffCaughtStealing { description, .. } |
+            Play::WildPitch { description, .. } |
+            Play::RunnerOut { description, .. } |
+            Play::FieldOut { description, .. } |
+            Play::BatterOut { description, .. } |
+            Play::Balk { description, .. } |
+            Play::PassedBall { description, .. } |
+            Play::Error { description, .. } |
+            Play::Single { description, .. } |
+            Play::Double { description, .. } |
+            Play::Triple { description, .. } |
+            Play::HomeRun { description, .. } |
+            Play::Walk { description, .. } |
+            Play::IntentWalk { description, .. } |
+            Play::HitByPitch { description, .. } |
+            Play::FieldersChoice { description, .. } |
+            Play::CatcherInterference { description, .. } |
+            Play::BatterInterference { description, .. } |
+            Play::FanInterference { description, .. } |
+            Play::StolenBase { description, .. } |
+            Play::SacFly { description, .. } |
+            Play::SacFlyDoublePlay { description, .. } |
+            Play::SacBunt { description, .. } |
+            Play::SacBuntDoublePlay { description, .. } |
+            Play::FieldError { description, .. } |
+            Play::DefensiveIndifference { description, .. } |
+            Play::PickoffAttempt { description, .. } |
+            Play::PitchingChange { description, .. } |
+            Play::MoundVisit { description, .. } |
+            Play::GameAdvisory { description, .. } |
+            Play::AutomaticRunner { description, .. } |
+            Play::Ejection { description, .. } => description.as_deref(),
+        }
+    }
 
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Caught Stealing\" }}\n{{ \"base\": \"{}\", \"runner\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    base_string,
-                    runner,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::PickoffCaughtStealing { inning, base, runner, fielders, movements } => {
-                let mut base_string = "home".to_string();
-                if *base != 4 {
-                    base_string = base.to_string();
-                }
+    /// The movements of the runners during the play, if it records any.
+    pub fn movements(&self) -> &[Movement] {
+        match self {
+            Play::Groundout { movements, .. } |
+            Play::BuntGroundout { movements, .. } |
+            Play::Strikeout { movements, .. } |
+            Play::Lineout { movements, .. } |
+            Play::BuntLineout { movements, .. } |
+            Play::Flyout { movements, .. } |
+            Play::PopOut { movements, .. } |
+            Play::BuntPopOut { movements, .. } |
+            Play::Forceout { movements, .. } |
+            Play::FieldersChoiceOut { movements, .. } |
+            Play::DoublePlay { movements, .. } |
+            Play::TriplePlay { movements, .. } |
+            Play::RunnerDoublePlay { movements, .. } |
+            Play::RunnerTriplePlay { movements, .. } |
+            Play::GroundedIntoDoublePlay { movements, .. } |
+            Play::StrikeoutDoublePlay { movements, .. } |
+            Play::Pickoff { movements, .. } |
+            Play::PickoffError { movements, .. } |
+            Play::CaughtStealing { movements, .. } |
+            Play::PickoffCaughtStealing { movements, .. } |
+            Play::WildPitch { movements, .. } |
+            Play::RunnerOut { movements, .. } |
+            Play::FieldOut { movements, .. } |
+            Play::BatterOut { movements, .. } |
+            Play::Balk { movements, .. } |
+            Play::PassedBall { movements, .. } |
+            Play::Error { movements, .. } |
+            Play::Single { movements, .. } |
+            Play::Double { movements, .. } |
+            Play::Triple { movements, .. } |
+            Play::HomeRun { movements, .. } |
+            Play::Walk { movements, .. } |
+            Play::IntentWalk { movements, .. } |
+            Play::HitByPitch { movements, .. } |
+            Play::FieldersChoice { movements, .. } |
+            Play::CatcherInterference { movements, .. } |
+            Play::BatterInterference { movements, .. } |
+            Play::FanInterference { movements, .. } |
+            Play::StolenBase { movements, .. } |
+            Play::SacFly { movements, .. } |
+            Play::SacFlyDoublePlay { movements, .. } |
+            Play::SacBunt { movements, .. } |
+            Play::SacBuntDoublePlay { movements, .. } |
+            Play::FieldError { movements, .. } |
+            Play::DefensiveIndifference { movements, .. } |
+            Play::PickoffAttempt { movements, .. } |
+            Play::Ejection { movements, .. } => movements,
+            _ => &[],
+        }
+    }
 
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Pickoff Caught Stealing\" }}\n{{ \"base\": \"{}\", \"runner\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    base_string,
-                    runner,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::WildPitch { inning, pitcher, runner, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Wild Pitch\" }}\n{{ \"pitcher\": \"{}\", \"runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    pitcher,
-                    runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::RunnerOut { inning, runner, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Runner Out\" }}\n{{ \"runner\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    runner,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::FieldOut { inning, fielder, runner, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Field Out\" }}\n{{ \"fielder\": \"{}\", \"runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    fielder,
-                    runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::BatterOut { inning, batter, catcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Batter Out\" }}\n{{ \"batter\": \"{}\", \"catcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    catcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Balk { inning, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Balk\" }}\n{{ \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::PassedBall { inning, pitcher, catcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Passed Ball\" }}\n{{ \"pitcher\": \"{}\", \"catcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    pitcher,
-                    catcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Error { inning, pitcher, catcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Error\" }}\n{{ \"pitcher\": \"{}\", \"catcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    pitcher,
-                    catcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Single { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Single\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Double { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Double\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Triple { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Triple\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::HomeRun { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Home Run\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::Walk { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Walk\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::IntentWalk { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Intent Walk\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::HitByPitch { inning, batter, pitcher, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Hit By Pitch\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::FieldersChoice { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Fielders Choice\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::CatcherInterference { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Catcher Interference\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
+    /// The hit data of the play, if it is a batted ball.
+    pub fn hit_data(&self) -> Option<&HitData> {
+        match self {
+            Play::Groundout { hit_data, .. } |
+            Play::BuntGroundout { hit_data, .. } |
+            Play::Lineout { hit_data, .. } |
+            Play::BuntLineout { hit_data, .. } |
+            Play::Flyout { hit_data, .. } |
+            Play::PopOut { hit_data, .. } |
+            Play::BuntPopOut { hit_data, .. } |
+            Play::Forceout { hit_data, .. } |
+            Play::FieldersChoiceOut { hit_data, .. } |
+            Play::DoublePlay { hit_data, .. } |
+            Play::TriplePlay { hit_data, .. } |
+            Play::GroundedIntoDoublePlay { hit_data, .. } |
+            Play::Single { hit_data, .. } |
+            Play::Double { hit_data, .. } |
+            Play::Triple { hit_data, .. } |
+            Play::HomeRun { hit_data, .. } |
+            Play::FieldersChoice { hit_data, .. } |
+            Play::FanInterference { hit_data, .. } |
+            Play::SacFly { hit_data, .. } |
+            Play::SacFlyDoublePlay { hit_data, .. } |
+            Play::SacBunt { hit_data, .. } |
+            Play::SacBuntDoublePlay { hit_data, .. } |
+            Play::FieldError { hit_data, .. } => Some(hit_data),
+            _ => None,
+        }
+    }
+
+    /// What a batted ball came to, from its movements: how many bases the batter reached and how many runs scored.
+    /// `None` if the play is not a batted ball.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.hit_data()?;
+
+        let movements = self.movements();
+        // the batter's movements start at home, and may continue from a base later in the play (e.g. on a throwing error)
+        let batter = movements.iter().find(|movement| movement.start_base.is_none()).map(|movement| movement.runner.as_str());
+        let batter_movements = movements.iter().filter(|movement| Some(movement.runner.as_str()) == batter);
+        let total_bases = if batter_movements.clone().any(|movement| movement.is_out) {
+            0
+        } else {
+            batter_movements.filter_map(|movement| movement.end_base).max().unwrap_or(0)
+        };
+        let runs = movements.iter().filter(|movement| movement.end_base == Some(4) && !movement.is_out).count() as u8;
+
+        Some(Outcome { total_bases, runs })
+    }
+
+    /// The pitcher who was pitching during the play, if the play records one.
+    pub fn pitcher(&self) -> Option<&str> {
+        match self {
+            Play::Groundout { pitcher, .. } |
+            Play::BuntGroundout { pitcher, .. } |
+            Play::Strikeout { pitcher, .. } |
+            Play::Lineout { pitcher, .. } |
+            Play::BuntLineout { pitcher, .. } |
+            Play::Flyout { pitcher, .. } |
+            Play::PopOut { pitcher, .. } |
+            Play::BuntPopOut { pitcher, .. } |
+            Play::Forceout { pitcher, .. } |
+            Play::FieldersChoiceOut { pitcher, .. } |
+            Play::DoublePlay { pitcher, .. } |
+            Play::TriplePlay { pitcher, .. } |
+            Play::RunnerDoublePlay { pitcher, .. } |
+            Play::RunnerTriplePlay { pitcher, .. } |
+            Play::GroundedIntoDoublePlay { pitcher, .. } |
+            Play::StrikeoutDoublePlay { pitcher, .. } |
+            Play::WildPitch { pitcher, .. } |
+            Play::Balk { pitcher, .. } |
+            Play::PassedBall { pitcher, .. } |
+            Play::Error { pitcher, .. } |
+            Play::Single { pitcher, .. } |
+            Play::Double { pitcher, .. } |
+            Play::Triple { pitcher, .. } |
+            Play::HomeRun { pitcher, .. } |
+            Play::Walk { pitcher, .. } |
+            Play::IntentWalk { pitcher, .. } |
+            Play::HitByPitch { pitcher, .. } |
+            Play::FieldersChoice { pitcher, .. } |
+            Play::CatcherInterference { pitcher, .. } |
+            Play::BatterInterference { pitcher, .. } |
+            Play::FanInterference { pitcher, .. } |
+            Play::SacFly { pitcher, .. } |
+            Play::SacFlyDoublePlay { pitcher, .. } |
+            Play::SacBunt { pitcher, .. } |
+            Play::SacBuntDoublePlay { pitcher, .. } |
+            Play::FieldError { pitcher, .. } |
+            Play::PickoffAttempt { pitcher, .. } => Some(pitcher),
+            Play::PitchingChange { new_pitcher, .. } => Some(new_pitcher),
+            _ => None,
+        }
+    }
+
+    /// Parse a pitching change or mound visit from one of a play's `playEvents`, if it is one.
+    async fn from_play_event(value: &serde_json::Value, event: &serde_json::Value) -> Option<Result<Self, String>> {
+        let inning = match Inning::from_value(&value["about"]) {
+            Ok(inning) => inning,
+            Err(e) => return Some(Err(e)),
+        };
+        let description = event["details"]["description"].as_str().map(str::to_string);
+
+        match event["details"]["eventType"].as_str()? {
+            "pitching_substitution" => {
+                let new_pitcher = match event["player"]["id"].as_u64() {
+                    Some(id) => get_player_name_from_id(id as usize).await,
+                    None => Err("No new pitcher".to_string()),
+                };
+
+                Some(new_pitcher.map(|new_pitcher| Play::PitchingChange { inning, description, new_pitcher }))
+            },
+            "mound_visit" => Some(Ok(Play::MoundVisit { inning, description })),
+            _ => None,
+        }
+    }
+
+    /// Parse a play, preceded by any pitching changes and mound visits that happened during it.
+    pub async fn all_from_value(value: &serde_json::Value) -> Result<Vec<Self>, String> {
+        let mut plays = Vec::new();
+        for event in value["playEvents"].as_array().into_iter().flatten() {
+            if let Some(play) = Play::from_play_event(value, event).await {
+                plays.push(play?);
             }
-            Play::StolenBase { inning, base, runner, movements } => {
-                let mut base_string = "home".to_string();
-                if *base != 4 {
-                    base_string = base.to_string();
+        }
+        plays.push(Play::from_value(value).await?);
+
+        Ok(plays)
+    }
+}
+
+impl Preprocess for Play {
+    /// The play's lines, as a JSON array of them.
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value {
+        let player = |name: &str| serde_json::Value::from(options.player(name));
+        let optional_player = |name: &Option<String>| name.as_deref().map_or(serde_json::Value::Null, player);
+        let players = |names: &[String]| names.iter().map(|name| player(name)).collect::<serde_json::Value>();
+        let base_name = |base: &u8| serde_json::Value::from(if *base == 4 { "home".to_string() } else { base.to_string() });
+
+        let introduction = serde_json::json!({ "inning": self.inning().to_value(options), "type": self.play_type() });
+
+        // the introduction line, followed by an information line with the given fields
+        // and a movement line, if the play has them
+        macro_rules! play_lines {
+            ($({ $($key:literal: $value:expr),+ $(,)? })? $(,)? $($movements:ident)? $(,)?) => {{
+                #[allow(unused_mut)]
+                let mut lines = vec![introduction];
+                $(
+                    let mut fields = serde_json::Map::new();
+                    $(fields.insert($key.to_string(), $value);)+
+                    lines.push(serde_json::Value::Object(fields));
+                )?
+                $(
+                    if !options.no_movements {
+                        let movements = $movements.iter().map(|movement| movement.to_value(options)).collect::<Vec<_>>();
+                        lines.push(serde_json::json!({ "movements": movements }));
+                    }
+                )?
+                lines
+            }};
+        }
+
+        let mut lines = match self {
+            Play::Groundout { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::BuntGroundout { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::Strikeout { batter, pitcher, movements, .. } => play_lines!({ "batter": player(batter), "pitcher": player(pitcher) }, movements),
+            Play::Lineout { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::BuntLineout { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::Flyout { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::PopOut { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::BuntPopOut { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::Forceout { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::FieldersChoiceOut { batter, pitcher, fielders, scoring_runner, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "scoring_runner": player(scoring_runner), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::DoublePlay { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::TriplePlay { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::RunnerDoublePlay { batter, pitcher, fielders, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::RunnerTriplePlay { batter, pitcher, fielders, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::GroundedIntoDoublePlay { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::StrikeoutDoublePlay { batter, pitcher, fielders, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::Pickoff { base, runner, fielders, movements, .. } => play_lines!(
+                { "base": base_name(base), "runner": player(runner), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::PickoffError { base, runner, fielders, movements, .. } => play_lines!(
+                { "base": base_name(base), "runner": player(runner), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::CaughtStealing { base, runner, fielders, movements, .. } => play_lines!(
+                { "base": base_name(base), "runner": player(runner), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::PickoffCaughtStealing { base, runner, fielders, movements, .. } => play_lines!(
+                { "base": base_name(base), "runner": player(runner), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::WildPitch { pitcher, runner, movements, .. } => play_lines!({ "pitcher": player(pitcher), "runner": player(runner) }, movements),
+            Play::RunnerOut { runner, fielders, movements, .. } => play_lines!(
+                { "runner": player(runner), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::FieldOut { fielder, runner, movements, .. } => play_lines!({ "fielder": player(fielder), "runner": player(runner) }, movements),
+            Play::BatterOut { batter, catcher, movements, .. } => play_lines!({ "batter": player(batter), "catcher": optional_player(catcher) }, movements),
+            Play::Balk { pitcher, movements, .. } => play_lines!({ "pitcher": player(pitcher) }, movements),
+            Play::PassedBall { pitcher, catcher, movements, .. } => play_lines!(
+                { "pitcher": player(pitcher), "catcher": player(catcher) },
+                movements,
+            ),
+            Play::Error { pitcher, catcher, movements, .. } => play_lines!({ "pitcher": player(pitcher), "catcher": player(catcher) }, movements),
+            Play::Single { batter, pitcher, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::Double { batter, pitcher, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::Triple { batter, pitcher, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::HomeRun { batter, pitcher, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::Walk { batter, pitcher, movements, .. } => play_lines!({ "batter": player(batter), "pitcher": player(pitcher) }, movements),
+            Play::IntentWalk { batter, pitcher, movements, .. } => play_lines!({ "batter": player(batter), "pitcher": player(pitcher) }, movements),
+            Play::HitByPitch { batter, pitcher, movements, .. } => play_lines!({ "batter": player(batter), "pitcher": player(pitcher) }, movements),
+            Play::FieldersChoice { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::CatcherInterference { batter, pitcher, fielders, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders) },
+                movements,
+            ),
+            Play::BatterInterference { batter, pitcher, movements, .. } => play_lines!({ "batter": player(batter), "pitcher": player(pitcher) }, movements),
+            Play::FanInterference { batter, pitcher, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::StolenBase { base, runner, other_steals, movements, .. } => {
+                let mut lines = play_lines!({ "base": base_name(base), "runner": player(runner) }, movements);
+                if !other_steals.is_empty() {
+                    let other_steals = other_steals
+                        .iter()
+                        .map(|steal| serde_json::json!({ "base": base_name(&steal.base), "runner": player(&steal.runner) }))
+                        .collect::<Vec<_>>();
+                    lines[1].as_object_mut().unwrap().insert("other_steals".to_string(), other_steals.into());
                 }
 
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Stolen Base\" }}\n{{ \"base\": \"{}\", \"runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    base_string,
-                    runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::SacFly { inning, batter, pitcher, fielders, scoring_runner, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Sac Fly\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}], \"scoring_runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    scoring_runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::SacFlyDoublePlay { inning, batter, pitcher, fielders, scoring_runner, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Sac Fly Double Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}], \"scoring_runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    scoring_runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::SacBunt { inning, batter, pitcher, fielders, runner, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Sac Bunt\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}], \"runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::SacBuntDoublePlay { inning, batter, pitcher, fielders, runner, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Sac Bunt Double Play\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}], \"runner\": \"{}\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    runner,
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::FieldError { inning, batter, pitcher, fielders, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Field Error\" }}\n{{ \"batter\": \"{}\", \"pitcher\": \"{}\", \"fielders\": [{}] }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    batter,
-                    pitcher,
-                    fielders.iter().map(|fielder| format!("\"{fielder}\"")).collect::<Vec<String>>().join(", "),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
-            Play::GameAdvisory { inning } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Game Advisory\" }}",
-                    inning.preprocess(),
-                )
-            }
-            Play::Ejection { inning, movements } => {
-                format!(
-                    "{{ \"inning\": {}, \"type\": \"Ejection\" }}\n{{ \"movements\": [{}] }}",
-                    inning.preprocess(),
-                    movements.iter().map(|movement| movement.preprocess()).collect::<Vec<String>>().join(", "),
-                )
-            }
+                lines
+            },
+            Play::SacFly { batter, pitcher, fielders, scoring_runner, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "scoring_runner": optional_player(scoring_runner), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::SacFlyDoublePlay { batter, pitcher, fielders, scoring_runner, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "scoring_runner": optional_player(scoring_runner), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::SacBunt { batter, pitcher, fielders, runner, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "runner": optional_player(runner), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::SacBuntDoublePlay { batter, pitcher, fielders, runner, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "runner": optional_player(runner), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::FieldError { batter, pitcher, fielders, hit_data, movements, .. } => play_lines!(
+                { "batter": player(batter), "pitcher": player(pitcher), "fielders": players(fielders), "hit_data": hit_data.to_value(options) },
+                movements,
+            ),
+            Play::DefensiveIndifference { runner, movements, .. } => play_lines!({ "runner": player(runner) }, movements),
+            Play::PickoffAttempt { base, pitcher, runner, movements, .. } => play_lines!(
+                { "base": base_name(base), "pitcher": player(pitcher), "runner": player(runner) },
+                movements,
+            ),
+            Play::PitchingChange { new_pitcher, .. } => play_lines!({ "pitcher": player(new_pitcher) }),
+            Play::MoundVisit { .. } => play_lines!(),
+            // the description is what the advisory is about, so it is always emitted
+            Play::GameAdvisory { description, .. } => play_lines!({ "note": description.as_deref().map(|note| options.text(note)).into() }),
+            Play::AutomaticRunner { runner, .. } => play_lines!({ "runner": player(runner) }),
+            Play::Ejection { ejected, reason, movements, .. } => play_lines!(
+                { "ejected": optional_player(ejected), "reason": reason.as_deref().map(|reason| options.text(reason)).into() },
+                movements,
+            ),
+        };
+
+        // the description goes on the introduction line, if requested and recorded (a game advisory's is already its note)
+        if let Some(description) = self.description().filter(|_| options.descriptions && !matches!(self, Play::GameAdvisory { .. })) {
+            lines[0].as_object_mut().unwrap().insert("description".to_string(), options.text(description).into());
+        }
+        if let Some(outcome) = self.outcome().filter(|_| options.outcomes) {
+            let introduction = lines[0].as_object_mut().unwrap();
+            introduction.insert("total_bases".to_string(), outcome.total_bases.into());
+            introduction.insert("runs".to_string(), outcome.runs.into());
+        }
+
+        serde_json::Value::Array(lines)
+    }
+
+    /// The play's lines, one per line.
+    fn preprocess(&self, options: &PreprocessOptions) -> String {
+        options.formatter().format_play(self, options)
+    }
+}
+
+/// Options controlling how games are got and parsed.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Parse games that are still in progress, up to their last completed play.
+    pub allow_live: bool,
+    /// Skip plays that fail to parse instead of discarding the whole game,
+    /// as long as at least this fraction of the game's plays parse.
+    pub min_completeness: Option<f64>,
+    /// Keep games whose boxscore cannot be got or parsed, with a context from the live feed alone
+    /// (see [`GameContext::from_feed_data_and_date_and_weather_and_game_pk`]).
+    pub optional_boxscore: bool,
+    /// Save the feed and boxscore each game is parsed from next to it, when getting and saving games.
+    pub save_raw: bool,
+}
+
+/// Why a saved game could not be loaded.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file's extension is not that of a saved game.
+    UnknownFormat(std::path::PathBuf),
+    /// The file could not be read (or decompressed).
+    Read(std::path::PathBuf, std::io::Error),
+    /// The file is not a valid game, e.g. because it was only partly written.
+    Parse(std::path::PathBuf, String),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LoadError::UnknownFormat(path) => write!(f, "Unknown game format: {}", path.display()),
+            LoadError::Read(path, e) => write!(f, "Failed to read {}: {e}", path.display()),
+            LoadError::Parse(path, e) => write!(f, "Failed to parse {}: {e}", path.display()),
+        }
+    }
+}
+
+/// Why a game could not be fetched.
+#[derive(Debug)]
+pub enum GameError {
+    /// The game is not final, and live games were not allowed.
+    NotFinal,
+    /// A request for the game's data failed.
+    Network(String),
+    /// The game's data was missing something or could not be parsed.
+    Parse(String),
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GameError::NotFinal => write!(f, "Game is not final"),
+            GameError::Network(e) => write!(f, "{e}"),
+            GameError::Parse(e) => write!(f, "{e}"),
         }
     }
 }
 
+impl From<String> for GameError {
+    fn from(e: String) -> Self {
+        GameError::Parse(e)
+    }
+}
+
+/// A game on the schedule of a season.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledGame {
+    pub game_pk: usize,
+    pub home_team_id: u32,
+    pub away_team_id: u32,
+    /// The game's detailed status when the schedule was fetched (e.g. `Final`, `Scheduled` or `Postponed`).
+    pub status: String,
+}
+
+impl ScheduledGame {
+    pub fn from_value(value: &serde_json::Value) -> Result<Self, String> {
+        let game_pk = value["gamePk"].as_u64().ok_or("No game pk in schedule")? as usize;
+        let team_id = |side: &str| value["teams"][side]["team"]["id"].as_u64().map(|id| id as u32).ok_or(format!("No {side} team in schedule"));
+        let status = value["status"]["detailedState"].as_str().ok_or("No status in schedule")?.to_string();
+
+        Ok(Self {
+            game_pk,
+            home_team_id: team_id("home")?,
+            away_team_id: team_id("away")?,
+            status,
+        })
+    }
+
+    /// Every game on the schedule of the given sport in the given season, of every team, fetched with one request.
+    /// A game that was moved to another date (e.g. postponed) is listed once, as it is on the later date.
+    pub async fn all_in_season(sport_id: u32, season: u16) -> Result<Vec<Self>, String> {
+        let url = format!("https://statsapi.mlb.com/api/v1/schedule?sportId={sport_id}&season={season}");
+        let schedule = crate::api::get_json(&url)
+            .await
+            .map_err(|e| format!("Failed to fetch schedule: {e}"))?;
+
+        let mut games: Vec<Self> = Vec::new();
+        for game_data in schedule["dates"]
+            .as_array()
+            .ok_or("No dates in schedule")?
+            .iter()
+            .flat_map(|date| date["games"].as_array().into_iter().flatten())
+        {
+            let game = ScheduledGame::from_value(game_data)?;
+            games.retain(|other| other.game_pk != game.game_pk);
+            games.push(game);
+        }
+
+        Ok(games)
+    }
+
+    /// Whether the game was over when the schedule was fetched.
+    pub fn is_final(&self) -> bool {
+        Game::is_final_status(&self.status)
+    }
+
+    /// The game pks of each of the given teams' games on `schedule`, in the same order as `team_ids`.
+    /// Each game is only listed for the first of its teams in `team_ids`, so that getting every team's games gets it once.
+    pub fn by_team(schedule: &[Self], team_ids: &[u32]) -> Vec<Vec<usize>> {
+        let mut listed = std::collections::HashSet::new();
+
+        team_ids
+            .iter()
+            .map(|team_id| {
+                schedule
+                    .iter()
+                    .filter(|game| game.home_team_id == *team_id || game.away_team_id == *team_id)
+                    .filter(|game| listed.insert(game.game_pk))
+                    .map(|game| game.game_pk)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The game pks of the final games on `schedule` that are not saved, in the order they were played.
+    pub fn missing(schedule: &[Self], is_saved: impl Fn(usize) -> bool) -> Vec<usize> {
+        schedule.iter().filter(|game| game.is_final() && !is_saved(game.game_pk)).map(|game| game.game_pk).collect()
+    }
+
+    /// The teams in `team_ids` that have a final game on `schedule` that is not saved (see [`ScheduledGame::missing`]),
+    /// in the same order.
+    pub fn teams_missing_games(schedule: &[Self], team_ids: &[u32], is_saved: impl Fn(usize) -> bool) -> Vec<u32> {
+        let missing = schedule.iter().filter(|game| game.is_final() && !is_saved(game.game_pk)).collect::<Vec<_>>();
+
+        team_ids
+            .iter()
+            .copied()
+            .filter(|team_id| missing.iter().any(|game| game.home_team_id == *team_id || game.away_team_id == *team_id))
+            .collect()
+    }
+}
+
+/// A step in getting a team's games, reported by [`Game::get_all_by_team_in_season`].
+#[derive(Debug)]
+pub enum GameProgress {
+    /// The team's games are about to be fetched, and there are this many of them (including those that will be skipped).
+    Scheduled { games: usize },
+    /// A game is about to be fetched.
+    Started { game_pk: usize },
+    /// A game was fetched and saved.
+    Saved { game_pk: usize },
+    /// A game was skipped because it is already saved.
+    Skipped { game_pk: usize },
+    /// A game could not be fetched or parsed, and was not saved.
+    Failed { game_pk: usize, error: GameError },
+}
+
+impl GameProgress {
+    /// The step of a game having been got, or having failed to be.
+    pub fn from_result(game_pk: usize, result: Result<(), GameError>) -> Self {
+        match result {
+            Ok(()) => GameProgress::Saved { game_pk },
+            Err(error) => GameProgress::Failed { game_pk, error },
+        }
+    }
+}
+
+/// How many games a run fetched, and how many it did not and why.
+#[derive(Debug, Default, Serialize)]
+pub struct FetchSummary {
+    pub fetched: usize,
+    pub skipped_existing: usize,
+    pub not_final: usize,
+    pub parse_error: usize,
+    pub network_error: usize,
+}
+
+impl FetchSummary {
+    /// Count the outcome of getting a game.
+    pub fn record(&mut self, result: &Result<(), GameError>) {
+        match result {
+            Ok(()) => self.fetched += 1,
+            Err(GameError::NotFinal) => self.not_final += 1,
+            Err(GameError::Network(_)) => self.network_error += 1,
+            Err(GameError::Parse(_)) => self.parse_error += 1,
+        }
+    }
+}
+
+impl std::ops::AddAssign for FetchSummary {
+    fn add_assign(&mut self, other: Self) {
+        self.fetched += other.fetched;
+        self.skipped_existing += other.skipped_existing;
+        self.not_final += other.not_final;
+        self.parse_error += other.parse_error;
+        self.network_error += other.network_error;
+    }
+}
+
+impl std::fmt::Display for FetchSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} fetched, {} skipped (already saved), {} not final, {} parse errors, {} network errors",
+            self.fetched,
+            self.skipped_existing,
+            self.not_final,
+            self.parse_error,
+            self.network_error,
+        )
+    }
+}
+
+/// The version of the saved game format. Bump it whenever a change to `Game` (or anything in it) changes how games
+/// are saved, so that `migrate` rewrites older games, and add the migration to it to [`MIGRATIONS`].
+pub const SCHEMA_VERSION: u32 = 6;
+
+/// What it takes to bring a game saved with an older schema version up to a newer one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Migration {
+    /// Saving the game again, since whatever it lacks has the right default.
+    Rewrite,
+    /// Parsing the game again, since it lacks data only its responses have.
+    Reparse,
+}
+
+/// The migration to each schema version from the one before it, by version.
+const MIGRATIONS: &[(u32, Migration)] = &[
+    // the version was not recorded before
+    (1, Migration::Rewrite),
+    // games gained the at-bat of each play
+    (2, Migration::Reparse),
+    // games gained the position each fielder was credited at
+    (3, Migration::Reparse),
+    // Ejection plays gained who was ejected and why
+    (4, Migration::Reparse),
+    // Stolen Base plays gained the other runners of a double steal
+    (5, Migration::Reparse),
+    // games gained the replay review of each play
+    (6, Migration::Reparse),
+];
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Game {
+    /// The `SCHEMA_VERSION` the game was saved with, or 0 for games saved before it was recorded.
+    #[serde(default)]
+    pub schema_version: u32,
     pub context: GameContext,
     pub plays: Vec<Play>,
+    /// The pitches thrown during each play, in the same order as `plays`. Empty for plays without pitches,
+    /// and for games saved before pitches were parsed.
+    #[serde(default)]
+    pub pitches: Vec<Vec<Pitch>>,
+    /// The feed's `atBatIndex` of the plate appearance each play happened during, in the same order as `plays`.
+    /// `None` for automatic runners, and empty for games saved before at-bats were parsed.
+    #[serde(default)]
+    pub at_bats: Vec<Option<usize>>,
+    /// The position number each fielder of each play was credited at, in the same order as `plays` and their fielders.
+    /// Empty for games saved before fielder positions were parsed.
+    #[serde(default)]
+    pub fielder_positions: Vec<Vec<Option<u8>>>,
+    /// The replay review of each play's call, in the same order as `plays`. `None` for plays that were not reviewed,
+    /// and empty for games saved before reviews were parsed.
+    #[serde(default)]
+    pub reviews: Vec<Option<Review>>,
+}
+
+/// What each play comes with in the feed besides the play itself, which [`Game`] keeps alongside its plays.
+#[derive(Debug, Default)]
+struct PlayExtras {
+    pitches: Vec<Pitch>,
+    at_bat: Option<usize>,
+    fielder_positions: Vec<Option<u8>>,
+    review: Option<Review>,
 }
 
 impl Game {
-    pub async fn from_game_pk(game_pk: usize) -> Result<Self, String> {
+    /// Whether a game with the given detailed status is over, however it ended
+    /// (e.g. `Final`, `Final: Tied`, `Game Over`, or `Completed Early: Rain`).
+    fn is_final_status(detailed_state: &str) -> bool {
+        detailed_state == "Final"
+            || detailed_state.starts_with("Final:")
+            || detailed_state == "Game Over"
+            || detailed_state.starts_with("Completed Early")
+    }
+
+    /// Get a game. Games that are not final are rejected unless `options.allow_live` is set,
+    /// in which case the plays completed so far are parsed.
+    pub async fn from_game_pk(game_pk: usize, options: &ParseOptions) -> Result<Self, GameError> {
+        let (game_data, boxscore_data) = Game::fetch_feed_and_boxscore(game_pk, options).await?;
+
+        Game::from_feed_and_boxscore(&game_data, &boxscore_data, options).await
+    }
+
+    /// Fetch the live feed and boxscore of a game, which [`Game::from_feed_and_boxscore`] parses. The boxscore is `null`
+    /// if it could not be fetched but `options.optional_boxscore` is set.
+    pub async fn fetch_feed_and_boxscore(game_pk: usize, options: &ParseOptions) -> Result<(serde_json::Value, serde_json::Value), GameError> {
         let url = format!("https://statsapi.mlb.com/api/v1.1/game/{game_pk}/feed/live");
-        log(format!("[Game::from_game_pk] Getting game: {url}"));
-        let response = match reqwest::get(&url).await {
-            Ok(response) => response,
-            Err(_) => return Err("Failed to fetch game data".to_string()),
+        tracing::info!(game_pk, %url, "Getting game");
+        let game_data = crate::api::get_json(&url)
+            .await
+            .map_err(|e| GameError::Network(format!("Failed to fetch game data: {e}")))?;
+
+        // checked before fetching the boxscore, which is not needed for a game that will be rejected
+        if !Game::feed_is_final(&game_data) && !options.allow_live {
+            return Err(GameError::NotFinal);
+        }
+
+        let boxscore_data_url = format!("https://statsapi.mlb.com/api/v1/game/{game_pk}/boxscore");
+        let boxscore_data = match crate::api::get_json(&boxscore_data_url).await {
+            Ok(boxscore_data) => boxscore_data,
+            Err(e) if options.optional_boxscore => {
+                tracing::warn!(game_pk, error = %e, "Failed to fetch boxscore data, parsing the game without it");
+                serde_json::Value::Null
+            },
+            Err(e) => return Err(GameError::Network(format!("Failed to fetch boxscore data: {e}"))),
         };
-        let game_data = response.json::<serde_json::Value>().await.unwrap();
 
-        let game_status = game_data["gameData"]["status"]["detailedState"].as_str();
-        if let Some("Final") = game_status {} else {
-            return Err("Game is not final".to_string());
+        Ok((game_data, boxscore_data))
+    }
+
+    /// Whether the game in the given live feed is over.
+    fn feed_is_final(game_data: &serde_json::Value) -> bool {
+        game_data["gameData"]["status"]["detailedState"].as_str().is_some_and(Game::is_final_status)
+    }
+
+    /// Parse a game from its live feed (`/api/v1.1/game/{game_pk}/feed/live`) and boxscore (`/api/v1/game/{game_pk}/boxscore`),
+    /// e.g. responses that were saved earlier.
+    pub async fn from_feed_and_boxscore(game_data: &serde_json::Value, boxscore_data: &serde_json::Value, options: &ParseOptions) -> Result<Self, GameError> {
+        let game_pk = game_data["gamePk"].as_u64().ok_or("No game pk".to_string())? as usize;
+        let is_final = Game::feed_is_final(game_data);
+        if !is_final && !options.allow_live {
+            return Err(GameError::NotFinal);
         }
 
-        let game_date = game_data["gameData"]["datetime"]["originalDate"].as_str().unwrap();
-        let game_date = Date::from(game_date);
+        let game_date = game_data["gameData"]["datetime"]["originalDate"].as_str().ok_or("No game date".to_string())?;
+        let game_date = Date::try_from(game_date)?;
+        // a suspended game is resumed on a later date, when it is officially played
+        let resumed_date = match game_data["gameData"]["datetime"]["resumeDate"].as_str() {
+            Some(resume_date) => Some(resume_date),
+            None => game_data["gameData"]["datetime"]["officialDate"].as_str(),
+        };
+        let resumed_date = resumed_date.map(Date::try_from).transpose()?.filter(|date| *date != game_date);
         let weather = Weather::from_value(&game_data["gameData"]["weather"]);
 
-        let plays_data = game_data["liveData"]["plays"]["allPlays"].as_array().unwrap();
+        let plays = Game::plays_from_feed(game_data, game_pk, is_final, options).await?;
+        let scheduled_innings = game_data["liveData"]["linescore"]["scheduledInnings"].as_u64().unwrap_or(9) as u8;
+
+        let weather = weather?;
+        let context = GameContext::from_game_boxscore_data_and_date_and_weather_and_game_pk(
+            boxscore_data,
+            game_date,
+            weather.clone(),
+            game_pk,
+        ).await;
+        let mut context = match context {
+            Ok(context) => context,
+            Err(e) if options.optional_boxscore => {
+                tracing::warn!(game_pk, error = %e, "Failed to parse boxscore data, parsing the game without it");
+                GameContext::from_feed_data_and_date_and_weather_and_game_pk(game_data, game_date, weather, game_pk)?
+            },
+            Err(e) => return Err(e.into()),
+        };
+        context.in_progress = !is_final;
+        context.resumed_date = resumed_date;
+        // the venue the game was actually played at, which is not the home team's for neutral-site games
+        if let Some(venue_id) = game_data["gameData"]["venue"]["id"].as_u64() {
+            context.venue_id = venue_id as usize;
+        }
+        context.location = Location::from_value(&game_data["gameData"]["venue"]["location"]);
+        context.day_night = game_data["gameData"]["datetime"]["dayNight"].as_str().and_then(DayNight::from_code);
+        context.start_time = game_data["gameData"]["datetime"]["dateTime"].as_str().map(str::to_string);
+        if let Some(game_type) = game_data["gameData"]["game"]["type"].as_str() {
+            context.game_type = GameType::from_code(game_type).ok_or(format!("Unknown game type: {game_type}"))?;
+        }
+        context.line_score = InningScore::line_score_from_value(&game_data["liveData"]["linescore"])?;
+        // runners have only been placed on second in extra innings of the regular season, since 2020
+        let places_automatic_runners = context.game_type == GameType::Regular && context.date.year >= 2020;
+        let (plays, extras): (Vec<Play>, Vec<PlayExtras>) =
+            Game::with_automatic_runners(plays, scheduled_innings, places_automatic_runners).into_iter().unzip();
+        // a game without a save (or in progress) has no `save` (or no `decisions`) at all
+        let decision = |decision: &str| game_data["liveData"]["decisions"][decision]["fullName"].as_str().map(str::to_string);
+        context.winning_pitcher = decision("winner");
+        context.losing_pitcher = decision("loser");
+        context.save_pitcher = decision("save");
+        if context.in_progress {
+            context.winner = None;
+            context.winning_pitcher = None;
+            context.losing_pitcher = None;
+            context.save_pitcher = None;
+        }
+
+        let mut game = Self {
+            schema_version: SCHEMA_VERSION,
+            context,
+            plays,
+            pitches: Vec::new(),
+            at_bats: Vec::new(),
+            fielder_positions: Vec::new(),
+            reviews: Vec::new(),
+        };
+        for PlayExtras { pitches, at_bat, fielder_positions, review } in extras {
+            game.pitches.push(pitches);
+            game.at_bats.push(at_bat);
+            game.fielder_positions.push(fielder_positions);
+            game.reviews.push(review);
+        }
+        for error in game.pitcher_continuity_errors() {
+            tracing::warn!(game_pk, "Pitcher continuity: {error}");
+        }
+
+        Ok(game)
+    }
+
+    /// Parse the plays of a game's live feed, each with the pitches thrown during it and its at-bat index, skipping the play under way
+    /// if the game is not final.
+    async fn plays_from_feed(
+        game_data: &serde_json::Value,
+        game_pk: usize,
+        is_final: bool,
+        options: &ParseOptions,
+    ) -> Result<Vec<(Play, PlayExtras)>, GameError> {
+        // a game without plays has no `allPlays` at all, rather than an empty list
+        let plays_data = game_data["liveData"]["plays"]["allPlays"].as_array().map(Vec::as_slice).unwrap_or_default();
 
         let mut plays = Vec::new();
+        let mut attempted_plays = 0;
+        let mut failed_plays = 0;
         for play in plays_data {
-            let p = Play::from_value(play).await?; // if any data is missing, discard the game
-            plays.push(p);
+            if !is_final && play["about"]["isComplete"].as_bool() != Some(true) {
+                continue; // the play currently under way in a live game
+            }
+
+            attempted_plays += 1;
+            match Play::all_from_value(play).await {
+                Ok(p) => {
+                    // the pitches and fielders belong to the play itself, not to the pitching changes and mound visits before it
+                    let at_bat = play["about"]["atBatIndex"].as_u64().map(|index| index as usize);
+                    let mut extras = std::iter::repeat_with(|| PlayExtras { at_bat, ..Default::default() }).take(p.len() - 1).collect::<Vec<_>>();
+                    extras.push(PlayExtras {
+                        pitches: Pitch::all_from_value(play),
+                        at_bat,
+                        fielder_positions: Play::fielder_positions_from_value(play),
+                        review: Review::from_value(play),
+                    });
+                    plays.extend(p.into_iter().zip(extras));
+                },
+                Err(e) if options.min_completeness.is_some() => {
+                    tracing::warn!(game_pk, error = %e, "Skipping play");
+                    failed_plays += 1;
+                },
+                Err(e) => return Err(e.into()), // if any data is missing, discard the game
+            }
         }
 
-        let boxscore_data_url = format!("https://statsapi.mlb.com/api/v1/game/{game_pk}/boxscore");
-        let boxscore_response = match reqwest::get(&boxscore_data_url).await {
-            Ok(response) => response,
-            Err(_) => return Err("Failed to fetch boxscore data".to_string()),
-        };
-        let boxscore_data = boxscore_response.json::<serde_json::Value>().await.unwrap();
-        let context = GameContext::from_game_boxscore_data_and_date_and_weather_and_game_pk(
-            &boxscore_data,
-            game_date,
-            weather?,
-            game_pk,
-        ).await?;
+        if let Some(min_completeness) = options.min_completeness {
+            let completeness = if attempted_plays == 0 { 1.0 } else { 1.0 - failed_plays as f64 / attempted_plays as f64 };
+            if completeness < min_completeness {
+                return Err(format!("{failed_plays} of {attempted_plays} plays failed to parse, below the minimum completeness of {min_completeness}").into());
+            }
+        }
 
-        Ok(Self { context, plays })
+        if plays.is_empty() {
+            return Err(GameError::Parse("No plays".to_string()));
+        }
+
+        Ok(plays)
     }
 
-    pub fn save(&self, game_pk: usize) {
-        std::fs::create_dir_all(format!(
-            "data/{}/{}",
-            self.context.date.year,
-            self.context.home_team.id,
-        )).map_err(|e| format!("Failed to create directories: {}", e)).unwrap();
+    /// Insert an `AutomaticRunner` play at the start of each extra half-inning that began with a runner placed on second.
+    /// The placed runner is the first to move from second base in the half-inning without anyone having reached it by a play.
+    /// Each play keeps the data it comes with, and the automatic runners get the default. Nothing is inserted unless
+    /// `places_automatic_runners` (i.e. the game is played under the rule).
+    fn with_automatic_runners<T: Default>(plays: Vec<(Play, T)>, scheduled_innings: u8, places_automatic_runners: bool) -> Vec<(Play, T)> {
+        if !places_automatic_runners {
+            return plays;
+        }
 
-        let file_path = format!(
-            "data/{}/{}/{}.json",
-            self.context.date.year,
-            self.context.home_team.id,
-            game_pk,
-        );
+        let mut plays_with_runners = Vec::with_capacity(plays.len());
+        let mut half_inning = None;
+        let mut half_inning_start = 0;
+        let mut second_base_reached = false;
+
+        for (play, data) in plays {
+            let Inning { number, top } = *play.inning();
+            if half_inning != Some((number, top)) {
+                half_inning = Some((number, top));
+                half_inning_start = plays_with_runners.len();
+                second_base_reached = false;
+            }
+
+            if number > scheduled_innings && !second_base_reached {
+                // a runner who reached second earlier in the same play (e.g. on a wild pitch during the at-bat) was not placed there
+                let movements = play.movements();
+                let placed_runner = movements
+                    .iter()
+                    .enumerate()
+                    .find(|(index, movement)| movement.start_base == Some(2) && !movements[..*index].iter().any(|earlier| earlier.end_base == Some(2)))
+                    .map(|(_, movement)| movement);
+                if let Some(movement) = placed_runner {
+                    plays_with_runners.insert(half_inning_start, (Play::AutomaticRunner {
+                        inning: Inning { number, top },
+                        description: None,
+                        runner: movement.runner.clone(),
+                    }, T::default()));
+                }
+            }
+            if play.movements().iter().any(|movement| movement.start_base == Some(2) || movement.end_base == Some(2)) {
+                second_base_reached = true;
+            }
 
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| format!("Failed to serialize game: {}", e)).unwrap();
-        std::fs::write(&file_path, json)
-            .map_err(|e| format!("Failed to write game to file: {}", e)).unwrap();
+            plays_with_runners.push((play, data));
+        }
 
-        log(format!("[Game::save] Saved game to {}", file_path));
+        plays_with_runners
     }
 
-    pub async fn get_all_by_team_in_season(team_id: u8, season: u16, skip_game_pks: Vec<usize>) -> Result<(), String> {
-        let url = format!("https://statsapi.mlb.com/api/v1/schedule?sportId=1&teamId={}&season={}", team_id, season);
-        let response = match reqwest::get(&url).await {
-            Ok(response) => response,
-            Err(_) => return Err("Failed to fetch team data".to_string()),
+    /// Save the game as `{data_dir}/{year}/{home_team_id}/{game_pk}.{extension}` (with the game type's directory after the year
+    /// for games outside the regular season), replacing any existing file atomically, and return the path it was saved to.
+    pub fn save(&self, game_pk: usize, data_dir: &str, format: GameFormat) -> String {
+        let season_dir = match self.context.game_type.dir_name() {
+            Some(game_type) => format!("{data_dir}/{}/{game_type}", self.context.date.year),
+            None => format!("{data_dir}/{}", self.context.date.year),
         };
-        let schedule = response.json::<serde_json::Value>().await.unwrap();
-        let dates = schedule["dates"].as_array().unwrap();
-
-        let progress_style = ProgressStyle::default_bar().template("{wide_bar} {pos}/{len} | elapsed: {elapsed_precise}, eta: {eta_precise}").unwrap();
-        for date in dates.iter().progress_with_style(progress_style) {
-            let games_data = date["games"].as_array().unwrap();
-            for game_data in games_data {
-                let game_pk = game_data["gamePk"].as_u64().unwrap() as usize;
-                if skip_game_pks.contains(&game_pk) {
-                    log(format!("[Game::get_all_by_team_in_season] Skipping game {}", game_pk));
-                    continue;
+        let file_path = format!("{season_dir}/{}/{game_pk}.{}", self.context.home_team.id, format.extension());
+        self.write(&file_path, format).unwrap();
+
+        tracing::info!(game_pk, path = %file_path, "Saved game");
+        file_path
+    }
+
+    /// Write the responses a game was parsed from next to the game saved at `game_path`
+    /// (see [`crate::storage::raw_response_path`]), so it can be parsed again without fetching it.
+    pub fn save_raw_responses(game_path: &str, game_data: &serde_json::Value, boxscore_data: &serde_json::Value) {
+        for (response, data) in [(crate::storage::RawResponse::Feed, game_data), (crate::storage::RawResponse::Boxscore, boxscore_data)] {
+            if data.is_null() {
+                continue; // an optional boxscore that could not be fetched
+            }
+            let path = crate::storage::raw_response_path(game_path, response);
+            crate::storage::write_atomic(&path, data.to_string()).unwrap();
+        }
+    }
+
+    /// Write the game to `path` in the given format (gzipped if `path` ends in `.gz`), replacing any existing file atomically.
+    pub fn write(&self, path: impl AsRef<std::path::Path>, format: GameFormat) -> Result<(), String> {
+        let contents = match format {
+            GameFormat::Json => serde_json::to_vec_pretty(self).map_err(|e| e.to_string()),
+            GameFormat::MessagePack => rmp_serde::to_vec_named(self).map_err(|e| e.to_string()),
+        };
+        let contents = contents.map_err(|e| format!("Failed to serialize game: {}", e))?;
+
+        crate::storage::write_game_file(path, contents).map_err(|e| format!("Failed to write game to file: {}", e))
+    }
+
+    /// Read a saved game, in the format given by its extension, decompressing it if it is gzipped.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, LoadError> {
+        let path = path.as_ref();
+        let format = GameFormat::from_path(path).ok_or_else(|| LoadError::UnknownFormat(path.to_path_buf()))?;
+        let contents = crate::storage::read_game_file(path).map_err(|e| LoadError::Read(path.to_path_buf(), e))?;
+
+        match format {
+            GameFormat::Json => serde_json::from_slice(&contents).map_err(|e| LoadError::Parse(path.to_path_buf(), e.to_string())),
+            GameFormat::MessagePack => rmp_serde::from_slice(&contents).map_err(|e| LoadError::Parse(path.to_path_buf(), e.to_string())),
+        }
+    }
+
+    /// Get a game and save it under `data_dir`, logging why if it could not be.
+    pub async fn get_and_save(game_pk: usize, data_dir: &str, format: GameFormat, options: &ParseOptions) -> Result<(), GameError> {
+        let result = match Game::fetch_feed_and_boxscore(game_pk, options).await {
+            Ok((game_data, boxscore_data)) => Game::from_feed_and_boxscore(&game_data, &boxscore_data, options).await.map(|game| {
+                let game_path = game.save(game_pk, data_dir, format);
+                if options.save_raw {
+                    Game::save_raw_responses(&game_path, &game_data, &boxscore_data);
                 }
+            }),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            tracing::error!(game_pk, error = %e, "Failed to get game");
+        }
 
-                match Game::from_game_pk(game_pk).await {
-                    Ok(game) => game.save(game_pk),
-                    Err(e) => log(format!("[Game::get_all_by_team_in_season] Error: {}", e)),
-                };
+        result
+    }
+
+    /// Get and save the given games of a team (from [`ScheduledGame::by_team`]), except those in `skip_game_pks`,
+    /// fetching up to `concurrency` games at once and reporting each step to `on_progress`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_all_by_team_in_season(
+        sport_id: u32,
+        team_id: u32,
+        game_pks: Vec<usize>,
+        skip_game_pks: Vec<usize>,
+        format: GameFormat,
+        options: &ParseOptions,
+        concurrency: usize,
+        on_progress: impl FnMut(GameProgress),
+    ) -> FetchSummary {
+        let data_dir = crate::storage::data_dir(sport_id);
+
+        // games are started from the stream and finished from the loop below, which never overlap
+        let on_progress = std::cell::RefCell::new(on_progress);
+        on_progress.borrow_mut()(GameProgress::Scheduled { games: game_pks.len() });
+
+        let mut summary = FetchSummary::default();
+        let (skipped_game_pks, game_pks) = game_pks.into_iter().partition::<Vec<usize>, _>(|game_pk| skip_game_pks.contains(game_pk));
+        for game_pk in skipped_game_pks {
+            tracing::info!(game_pk, team_id, "Skipping game");
+            summary.skipped_existing += 1;
+            on_progress.borrow_mut()(GameProgress::Skipped { game_pk });
+        }
+
+        let mut results = futures::stream::iter(game_pks)
+            .map(|game_pk| {
+                on_progress.borrow_mut()(GameProgress::Started { game_pk });
+                let data_dir = &data_dir;
+                async move { (game_pk, Game::get_and_save(game_pk, data_dir, format, options).await) }
+            })
+            .buffer_unordered(concurrency);
+        while let Some((game_pk, result)) = results.next().await {
+            summary.record(&result);
+            on_progress.borrow_mut()(GameProgress::from_result(game_pk, result));
+        }
+
+        summary
+    }
+}
+
+impl Game {
+    /// What it takes to bring the game up to [`SCHEMA_VERSION`] (the most of what each newer version takes), or `None`
+    /// if it is current.
+    pub fn migration(&self) -> Option<Migration> {
+        MIGRATIONS.iter().filter(|(version, _)| *version > self.schema_version).map(|(_, migration)| *migration).max()
+    }
+
+    /// The plays grouped by half-inning, in order. A half-inning that is interrupted and later resumed
+    /// (which only happens in malformed feeds) is split into two groups.
+    pub fn innings(&self) -> Vec<(Inning, Vec<&Play>)> {
+        let mut innings: Vec<(Inning, Vec<&Play>)> = Vec::new();
+        for play in &self.plays {
+            let inning = *play.inning();
+            match innings.last_mut() {
+                Some((last_inning, plays)) if *last_inning == inning => plays.push(play),
+                _ => innings.push((inning, vec![play])),
             }
         }
 
-        Ok(())
+        innings
+    }
+
+    /// Check that each play's pitcher is the current pitcher of record for the fielding team,
+    /// which only changes with a pitching change. Returns a description of each inconsistency.
+    pub fn pitcher_continuity_errors(&self) -> Vec<String> {
+        // the home team fields in the top of an inning, the away team in the bottom
+        let mut home_pitcher: Option<&str> = None;
+        let mut away_pitcher: Option<&str> = None;
+
+        let mut errors = Vec::new();
+        for (index, play) in self.plays.iter().enumerate() {
+            let inning = play.inning();
+            let current_pitcher = if inning.top { &mut home_pitcher } else { &mut away_pitcher };
+
+            match (play, play.pitcher()) {
+                (Play::PitchingChange { new_pitcher, .. }, _) => *current_pitcher = Some(new_pitcher),
+                (_, Some(pitcher)) => match current_pitcher {
+                    Some(current) if *current != pitcher => {
+                        errors.push(format!(
+                            "play {index} ({}): pitcher is {pitcher} but {current} is pitching without a pitching change",
+                            inning,
+                        ));
+                        *current_pitcher = Some(pitcher);
+                    },
+                    Some(_) => {},
+                    None => *current_pitcher = Some(pitcher),
+                },
+                (_, None) => {},
+            }
+        }
+
+        errors
+    }
+
+    /// Fill in the player tokens for this game's players, as requested by `options`.
+    fn player_options(&self, options: &PreprocessOptions) -> PreprocessOptions {
+        let mut options = options.clone();
+        if let Some(anonymize) = &options.anonymize {
+            let names = self.context.home_team.players
+                .iter()
+                .chain(self.context.away_team.players.iter())
+                .map(|player| player.name.clone())
+                .collect();
+            options.player_names = Some(anonymize.pseudonyms(names));
+        } else if options.player_ids {
+            let player_ids = self.context.home_team.players
+                .iter()
+                .chain(self.context.away_team.players.iter())
+                .filter(|player| player.id != 0)
+                .map(|player| (player.name.clone(), player.id.to_string()))
+                .collect();
+            options.player_names = Some(player_ids);
+        }
+
+        options
     }
 }
 
 impl Preprocess for Game {
-    fn preprocess(&self) -> String {
+    /// The context line and the lines of each play, as a JSON array of lines.
+    fn to_value(&self, options: &PreprocessOptions) -> serde_json::Value {
         // let mut tokens = String::new();
 
         // tokens += &format!("[GAME] {}\n[GAME_START]\n", self.context.preprocess());
@@ -2610,10 +4063,979 @@ impl Preprocess for Game {
         //     self.context.preprocess(),
         //     self.plays.iter().map(|play| play.preprocess()).collect::<Vec<String>>().join(", "),
         // )
-        format!(
-            "{}\n{}\n",
-            self.context.preprocess(),
-            self.plays.iter().map(|play| play.preprocess()).collect::<Vec<String>>().join("\n"),
-        )
+        let options = &self.player_options(options);
+
+        let re24s = options.run_expectancy.as_ref().map(|run_expectancy| run_expectancy.re24s(&self.plays));
+        let mut pitch_counts = std::collections::HashMap::<usize, usize>::new();
+        for (at_bat, pitches) in self.at_bats.iter().zip(&self.pitches) {
+            if let Some(at_bat) = at_bat {
+                *pitch_counts.entry(*at_bat).or_default() += pitches.len();
+            }
+        }
+        let mut last_at_bat = None;
+        let plays = self.plays.iter().enumerate().filter(|(_, play)| options.includes(play)).flat_map(|(index, play)| {
+            let serde_json::Value::Array(mut lines) = play.to_value(options) else { unreachable!("plays are arrays of lines") };
+
+            // the play's position in the game goes first on its introduction line
+            let introduction = lines[0].as_object_mut().unwrap();
+            introduction.shift_insert(0, "index".to_string(), index.into());
+            if let Some(review) = self.reviews.get(index).and_then(Option::as_ref) {
+                introduction.insert("review".to_string(), review.to_value(options));
+            }
+            if let Some(re24) = re24s.as_ref().and_then(|re24s| re24s[index]) {
+                // rounded to 3 decimal places
+                let re24 = format!("{re24:.3}").parse::<f64>().unwrap();
+                introduction.insert("re24".to_string(), re24.into());
+            }
+
+            if let Some(pitches) = self.pitches.get(index).filter(|pitches| options.pitches && !pitches.is_empty()) {
+                let pitches = pitches.iter().map(|pitch| pitch.to_value(options)).collect::<Vec<_>>();
+                lines.insert(1, serde_json::json!({ "pitches": pitches }));
+            }
+
+            if options.fielders != FielderFormat::Names {
+                let positions = self.fielder_positions.get(index).map(Vec::as_slice).unwrap_or_default();
+                for fielders in lines.iter_mut().filter_map(|line| line.get_mut("fielders")) {
+                    let serde_json::Value::Array(fielders) = fielders else { continue };
+                    // a fielder whose position is unknown (e.g. in a game saved before positions were parsed) gets null
+                    for (fielder_index, fielder) in fielders.iter_mut().enumerate() {
+                        let position = positions.get(fielder_index).copied().flatten();
+                        *fielder = match options.fielders {
+                            FielderFormat::Positions => position.into(),
+                            _ => serde_json::json!({ "position": position, "name": fielder.take() }),
+                        };
+                    }
+                }
+            }
+
+            // the at-bat line goes before the first (emitted) play of each plate appearance
+            let at_bat = self.at_bats.get(index).copied().flatten();
+            if let Some(at_bat) = at_bat.filter(|_| options.at_bats && at_bat != last_at_bat) {
+                lines.insert(0, serde_json::json!({ "at_bat": at_bat, "pitch_count": pitch_counts[&at_bat] }));
+            }
+            last_at_bat = at_bat;
+
+            lines
+        });
+
+        let lines = std::iter::once(self.context.to_value(options)).chain(plays).collect::<serde_json::Value>();
+        let lines = if options.key_names.is_empty() { lines } else { rename_keys(lines, &options.key_names) };
+
+        if options.lowercase { lowercase_json(lines) } else { lines }
+    }
+
+    /// The context line, followed by the lines of each play, one per line.
+    fn preprocess(&self, options: &PreprocessOptions) -> String {
+        options.formatter().format_game(self, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Answer requests only from the fixture cache, so tests never hit the network.
+    fn offline_api() {
+        static OFFLINE: std::sync::Once = std::sync::Once::new();
+        OFFLINE.call_once(|| crate::api::configure(crate::api::ApiConfig {
+            cache_dir: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/cache").into()),
+            offline: true,
+            ..Default::default()
+        }));
+    }
+
+    /// Parse the plays in `tests/fixtures/plays/{name}.json`.
+    async fn plays_from_fixture(name: &str) -> Vec<Play> {
+        offline_api();
+        let path = format!("{}/tests/fixtures/plays/{name}.json", env!("CARGO_MANIFEST_DIR"));
+        let value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        Play::all_from_value(&value).await.unwrap()
+    }
+
+    /// A game between the Yankees and the Dodgers made of the plays in the given fixtures.
+    async fn game_from_fixtures(names: &[&str]) -> Game {
+        let context = serde_json::json!({
+            "game_pk": 1,
+            "date": { "year": 2024, "month": 10, "day": 30 },
+            "venue_name": "Yankee Stadium",
+            "weather": { "condition": "Clear", "temperature": 56, "wind_speed": 7 },
+            "home_team": { "id": 147, "players": [] },
+            "away_team": { "id": 119, "players": [] },
+        });
+
+        let mut plays = Vec::new();
+        for name in names {
+            plays.extend(plays_from_fixture(name).await);
+        }
+
+        Game {
+            schema_version: SCHEMA_VERSION,
+            context: serde_json::from_value(context).unwrap(),
+            plays,
+            pitches: Vec::new(),
+            at_bats: Vec::new(),
+            fielder_positions: Vec::new(),
+            reviews: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn game_from_feed_and_boxscore() {
+        offline_api();
+        let fixture = |name: &str| {
+            let path = format!("{}/tests/fixtures/games/{name}.json", env!("CARGO_MANIFEST_DIR"));
+            serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(path).unwrap()).unwrap()
+        };
+
+        let game = Game::from_feed_and_boxscore(&fixture("feed"), &fixture("boxscore"), &ParseOptions::default()).await.unwrap();
+
+        assert_eq!(game.context.game_pk, 775296);
+        assert_eq!((game.context.home_team.id, game.context.away_team.id), (147, 119));
+        assert_eq!(game.context.venue_id, 3313);
+        assert_eq!(game.context.game_type, GameType::Postseason);
+        assert_eq!(game.context.line_score.len(), 3);
+        assert!(matches!(&game.plays[..], [Play::Single { .. }, Play::Strikeout { .. }, Play::HomeRun { .. }]), "{:?}", game.plays);
+        assert_eq!(game.context.winning_pitcher.as_deref(), Some("Walker Buehler"));
+        assert_eq!(game.context.losing_pitcher.as_deref(), Some("Gerrit Cole"));
+        assert_eq!(game.context.save_pitcher, None);
+        assert_eq!(game.fielder_positions, [vec![], vec![Some(4)], vec![]]);
+        assert_eq!(game.reviews, [Some(Review { outcome: ReviewOutcome::Overturned, challenge_team_id: Some(119) }), None, None]);
+    }
+
+    #[tokio::test]
+    async fn season_rosters_warm_the_player_names() {
+        offline_api();
+        let roster = Player::all_on_season_roster(147, 2024).await.unwrap();
+
+        assert_eq!(roster.len(), 3);
+        assert!(matches!(&roster[0], (543037, name, Some(Position::Pitcher)) if name == "Gerrit Cole"));
+        // a position that is not known is left out rather than failing the roster
+        assert!(matches!(&roster[2], (999001, name, None) if name == "Roster Only"));
+
+        // not in the fixture cache, so only found because it was added
+        assert!(get_player_name_from_id(999001).await.is_err());
+        cache_player_names(roster.into_iter().map(|(id, name, _)| (id, name)));
+        assert_eq!(get_player_name_from_id(999001).await.unwrap(), "Roster Only");
+    }
+
+    #[test]
+    fn automatic_runners_only_under_the_rule() {
+        let movement = |runner: &str, start_base, end_base| Movement { runner: runner.to_string(), start_base, end_base, is_out: false };
+        let single = |movements| Play::Single {
+            inning: Inning { number: 10, top: true },
+            description: None,
+            hit_data: HitData::default(),
+            batter: "Mookie Betts".to_string(),
+            pitcher: "Gerrit Cole".to_string(),
+            movements,
+        };
+        let is_automatic_runner = |play: &(Play, ())| matches!(play.0, Play::AutomaticRunner { .. });
+
+        // the runner on first went to second on a wild pitch during the at-bat, then scored on the single
+        let reached_second_during_the_at_bat = || vec![(single(vec![
+            movement("Justin Turner", Some(1), Some(2)),
+            movement("Justin Turner", Some(2), Some(4)),
+            movement("Mookie Betts", None, Some(1)),
+        ]), ())];
+        // e.g. a postseason game, which has no automatic runners
+        assert!(!Game::with_automatic_runners(reached_second_during_the_at_bat(), 9, false).iter().any(is_automatic_runner));
+        assert!(!Game::with_automatic_runners(reached_second_during_the_at_bat(), 9, true).iter().any(is_automatic_runner));
+
+        let placed_runner_scores = || vec![(single(vec![movement("Justin Turner", Some(2), Some(4)), movement("Mookie Betts", None, Some(1))]), ())];
+        let plays = Game::with_automatic_runners(placed_runner_scores(), 9, true);
+        assert!(matches!(&plays[0].0, Play::AutomaticRunner { runner, .. } if runner == "Justin Turner"));
+        assert_eq!(plays.len(), 2);
+        assert_eq!(Game::with_automatic_runners(placed_runner_scores(), 9, false).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn boxscore_can_be_optional() {
+        offline_api();
+        let path = format!("{}/tests/fixtures/games/feed.json", env!("CARGO_MANIFEST_DIR"));
+        let feed = serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let boxscore = serde_json::json!({ "teams": { "home": {}, "away": {} } });
+
+        assert!(Game::from_feed_and_boxscore(&feed, &boxscore, &ParseOptions::default()).await.is_err());
+
+        let options = ParseOptions { optional_boxscore: true, ..Default::default() };
+        let game = Game::from_feed_and_boxscore(&feed, &boxscore, &options).await.unwrap();
+        assert_eq!((game.context.home_team.id, game.context.away_team.id), (147, 119));
+        assert!(game.context.home_team.players.is_empty());
+        assert_eq!(game.context.home_final_score, None);
+        // what the feed has is still parsed
+        assert_eq!(game.context.venue_id, 3313);
+        assert_eq!(game.plays.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn season_schedule_lists_each_game_once() {
+        offline_api();
+        let schedule = ScheduledGame::all_in_season(1, 2024).await.unwrap();
+
+        // the postponed game is listed on the date it was played
+        assert_eq!(schedule.iter().map(|game| game.game_pk).collect::<Vec<usize>>(), [745001, 745002, 745003]);
+        assert!(schedule[1].is_final());
+        assert!(!schedule[2].is_final());
+
+        // each game is listed for the first of its teams
+        assert_eq!(ScheduledGame::by_team(&schedule, &[147, 111, 119]), [vec![745001, 745002], vec![745003], vec![]]);
+        assert_eq!(ScheduledGame::by_team(&schedule, &[119]), [vec![745001, 745003]]);
+    }
+
+    #[tokio::test]
+    async fn missing_games_are_final_and_not_saved() {
+        offline_api();
+        let schedule = ScheduledGame::all_in_season(1, 2024).await.unwrap();
+
+        // the scheduled game is not missing yet
+        assert_eq!(ScheduledGame::missing(&schedule, |_| false), [745001, 745002]);
+        assert_eq!(ScheduledGame::missing(&schedule, |game_pk| game_pk == 745001), [745002]);
+        assert!(ScheduledGame::missing(&schedule, |_| true).is_empty());
+
+        // 745002 is between 111 and 147, and 119 only has the final game that is saved and the one that is not final
+        assert_eq!(ScheduledGame::teams_missing_games(&schedule, &[111, 119, 147], |game_pk| game_pk == 745001), [111, 147]);
+        assert!(ScheduledGame::teams_missing_games(&schedule, &[111, 119, 147], |_| true).is_empty());
+    }
+
+    #[tokio::test]
+    async fn pitch_sequences() {
+        offline_api();
+        let fixture = |name: &str| {
+            let path = format!("{}/tests/fixtures/games/{name}.json", env!("CARGO_MANIFEST_DIR"));
+            serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(path).unwrap()).unwrap()
+        };
+        let game = Game::from_feed_and_boxscore(&fixture("feed"), &fixture("boxscore"), &ParseOptions::default()).await.unwrap();
+
+        let calls = game.pitches[1].iter().map(|pitch| pitch.call).collect::<Vec<_>>();
+        assert_eq!(calls, [PitchCall::Ball, PitchCall::Foul, PitchCall::Strike, PitchCall::Strike]);
+        assert_eq!(game.pitches[0][0].call, PitchCall::Pitchout);
+        assert!(game.preprocess(&PreprocessOptions { pitches: true, ..Default::default() }).contains("{ \"call\": \"Pitchout\", \"pitch_type\": null }"));
+        assert_eq!(game.pitches[1][2].pitch_type, None);
+
+        let preprocessed = game.preprocess(&PreprocessOptions { pitches: true, ..Default::default() });
+        let lines = preprocessed.lines().collect::<Vec<_>>();
+        assert!(lines[5].starts_with("{ \"index\": 1, "));
+        assert_eq!(
+            lines[6],
+            "{ \"pitches\": [{ \"call\": \"Ball\", \"pitch_type\": \"Slider\" }, { \"call\": \"Foul\", \"pitch_type\": \"Four-Seam Fastball\" }, \
+            { \"call\": \"Strike\", \"pitch_type\": null }, { \"call\": \"Strike\", \"pitch_type\": \"Sweeper\" }] }",
+        );
+        assert!(!game.preprocess(&PreprocessOptions::default()).contains("pitches"));
+    }
+
+    #[test]
+    fn pitchouts_and_intentional_balls_are_not_balls() {
+        let call = |code: &str| PitchCall::from_details(&serde_json::json!({ "call": { "code": code }, "isBall": true }));
+
+        assert_eq!(call("B"), Some(PitchCall::Ball));
+        assert_eq!(call("P"), Some(PitchCall::Pitchout));
+        assert_eq!(call("I"), Some(PitchCall::IntentionalBall));
+        // swung at or fouled off, they are strikes and fouls like any other pitch
+        assert_eq!(call("Q"), Some(PitchCall::Strike));
+        assert_eq!(call("R"), Some(PitchCall::Foul));
+    }
+
+    #[tokio::test]
+    async fn at_bats_count_their_pitches() {
+        offline_api();
+        let fixture = |name: &str| {
+            let path = format!("{}/tests/fixtures/games/{name}.json", env!("CARGO_MANIFEST_DIR"));
+            serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(path).unwrap()).unwrap()
+        };
+        let mut game = Game::from_feed_and_boxscore(&fixture("feed"), &fixture("boxscore"), &ParseOptions::default()).await.unwrap();
+        assert_eq!(game.at_bats, [Some(20), Some(21), Some(22)]);
+
+        let options = PreprocessOptions { at_bats: true, ..Default::default() };
+        let at_bat_lines = |game: &Game| {
+            game.preprocess(&options).lines().filter(|line| line.starts_with("{ \"at_bat\"")).map(str::to_string).collect::<Vec<_>>()
+        };
+        assert_eq!(at_bat_lines(&game), [
+            "{ \"at_bat\": 20, \"pitch_count\": 2 }",
+            "{ \"at_bat\": 21, \"pitch_count\": 4 }",
+            "{ \"at_bat\": 22, \"pitch_count\": 1 }",
+        ]);
+        assert!(!game.preprocess(&PreprocessOptions::default()).contains("at_bat"));
+
+        // plays of the same plate appearance share one line, which counts all of its pitches
+        game.at_bats = vec![Some(20), Some(20), Some(21)];
+        assert_eq!(at_bat_lines(&game), ["{ \"at_bat\": 20, \"pitch_count\": 6 }", "{ \"at_bat\": 21, \"pitch_count\": 1 }"]);
+    }
+
+    #[tokio::test]
+    async fn plays_are_grouped_by_half_inning() {
+        let mut game = game_from_fixtures(&["single", "strikeout"]).await;
+        let inning = |number, top| Inning { number, top };
+        game.plays.push(Play::MoundVisit { inning: inning(3, false), description: None });
+        game.plays.push(Play::GameAdvisory { inning: inning(4, true), description: None });
+        game.plays.push(Play::MoundVisit { inning: inning(4, true), description: None });
+
+        let innings = game.innings();
+
+        let sizes = innings.iter().map(|(inning, plays)| (inning.number, inning.top, plays.len())).collect::<Vec<_>>();
+        assert_eq!(sizes, [(3, true, 2), (3, false, 1), (4, true, 2)]);
+        assert!(matches!(innings[2].1[..], [Play::GameAdvisory { .. }, Play::MoundVisit { .. }]));
+    }
+
+    #[tokio::test]
+    async fn saved_games_load_in_every_format() {
+        let game = game_from_fixtures(&["single", "pickoff", "home_run"]).await;
+        let data_dir = std::env::temp_dir().join(format!("mlb-transformer-formats-{}", std::process::id()));
+
+        for format in GameFormat::ALL {
+            game.save(1, data_dir.to_str().unwrap(), format);
+            let loaded = Game::load(data_dir.join(format!("2024/147/1.{}", format.extension()))).unwrap();
+
+            assert_eq!(format!("{loaded:?}"), format!("{game:?}"));
+
+            let compressed_path = data_dir.join(format!("2024/147/1.{}.gz", format.extension()));
+            game.write(&compressed_path, format).unwrap();
+            let loaded = Game::load(&compressed_path).unwrap();
+
+            assert_eq!(format!("{loaded:?}"), format!("{game:?}"));
+            assert_eq!(crate::storage::game_pk_from_path(&compressed_path), Some(1));
+        }
+        std::fs::remove_dir_all(data_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn games_outside_the_regular_season_are_saved_under_their_type() {
+        let mut game = game_from_fixtures(&["single"]).await;
+        game.context.game_type = GameType::Postseason;
+        let data_dir = std::env::temp_dir().join(format!("mlb-transformer-game-types-{}", std::process::id()));
+
+        game.save(1, data_dir.to_str().unwrap(), GameFormat::Json);
+
+        assert!(data_dir.join("2024/postseason/147/1.json").exists());
+        std::fs::remove_dir_all(data_dir).unwrap();
+    }
+
+    #[test]
+    fn games_saved_before_schema_versions_load_as_version_0() {
+        let path = format!("{}/tests/fixtures/games/unversioned.json", env!("CARGO_MANIFEST_DIR"));
+
+        let game = Game::load(path).unwrap();
+
+        assert_eq!(game.schema_version, 0);
+        assert!(game.pitches.is_empty());
+        assert_eq!(game.migration(), Some(Migration::Reparse));
+    }
+
+    #[test]
+    fn every_schema_version_has_a_migration() {
+        let versions = MIGRATIONS.iter().map(|(version, _)| *version).collect::<Vec<u32>>();
+
+        assert_eq!(versions, (1..=SCHEMA_VERSION).collect::<Vec<u32>>());
+    }
+
+    #[tokio::test]
+    async fn compact_output() {
+        let game = game_from_fixtures(&["single"]).await;
+        let options = PreprocessOptions { descriptions: true, ..Default::default() };
+
+        let spaced = game.preprocess(&options);
+        let compact = game.preprocess(&PreprocessOptions { compact: true, ..options });
+
+        assert!(compact.contains("{\"index\":0,\"inning\":{\"number\":3,\"top\":true},\"type\":\"Single\""));
+        assert!(compact.contains("\"description\":\"Mookie Betts singles"));
+        assert_eq!(compact.lines().count(), spaced.lines().count());
+        for (compact, spaced) in compact.lines().zip(spaced.lines()) {
+            let parse = |line| serde_json::from_str::<serde_json::Value>(line).unwrap();
+            assert_eq!(parse(compact), parse(spaced));
+        }
+    }
+
+    #[tokio::test]
+    async fn game_advisory_description_is_only_its_note() {
+        let plays = plays_from_fixture("game_advisory").await;
+        let options = PreprocessOptions { descriptions: true, ..Default::default() };
+
+        let lines = plays.last().unwrap().to_value(&options);
+
+        assert_eq!(lines[0].get("description"), None);
+        assert_eq!(lines[1]["note"], "Status Change - Delayed: Rain");
+    }
+
+    #[tokio::test]
+    async fn double_steals_list_the_other_runners() {
+        let game = game_from_fixtures(&["stolen_base", "double_steal"]).await;
+        let preprocessed = game.preprocess(&PreprocessOptions::default());
+        let lines = preprocessed.lines().filter(|line| line.starts_with("{ \"base\"")).collect::<Vec<_>>();
+
+        assert_eq!(lines, [
+            "{ \"base\": \"2\", \"runner\": \"Justin Turner\" }",
+            "{ \"base\": \"2\", \"runner\": \"Justin Turner\", \"other_steals\": [{ \"base\": \"3\", \"runner\": \"Mookie Betts\" }] }",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn batted_ball_outcomes() {
+        let outcome = |name: &'static str| async move { plays_from_fixture(name).await.last().unwrap().outcome() };
+
+        assert_eq!(outcome("single").await, Some(Outcome { total_bases: 1, runs: 0 }));
+        assert_eq!(outcome("double").await, Some(Outcome { total_bases: 2, runs: 0 }));
+        assert_eq!(outcome("home_run").await, Some(Outcome { total_bases: 4, runs: 1 }));
+        assert_eq!(outcome("groundout").await, Some(Outcome { total_bases: 0, runs: 0 }));
+        assert_eq!(outcome("sac_fly").await, Some(Outcome { total_bases: 0, runs: 1 }));
+        // not a batted ball
+        assert_eq!(outcome("strikeout").await, None);
+
+        let game = game_from_fixtures(&["home_run", "strikeout"]).await;
+        let preprocessed = game.preprocess(&PreprocessOptions { outcomes: true, ..Default::default() });
+        assert!(preprocessed.contains("\"type\": \"Home Run\", \"total_bases\": 4, \"runs\": 1 }"), "{preprocessed}");
+        assert!(preprocessed.contains("\"type\": \"Strikeout\" }"), "{preprocessed}");
+        assert!(!game.preprocess(&PreprocessOptions::default()).contains("total_bases"));
+    }
+
+    #[test]
+    fn reviews_are_parsed_once_finished() {
+        let review = |details: serde_json::Value| Review::from_value(&serde_json::json!({ "playEvents": [{ "reviewDetails": details }, {}] }));
+
+        // the umpires reviewed it themselves
+        assert_eq!(
+            review(serde_json::json!({ "isOverturned": false, "inProgress": false, "reviewType": "MF" })),
+            Some(Review { outcome: ReviewOutcome::Upheld, challenge_team_id: None }),
+        );
+        assert_eq!(review(serde_json::json!({ "isOverturned": false, "inProgress": true, "challengeTeamId": 147 })), None);
+        assert_eq!(Review::from_value(&serde_json::json!({ "playEvents": [] })), None);
+    }
+
+    #[tokio::test]
+    async fn reviews_on_the_introduction_line() {
+        let mut game = game_from_fixtures(&["single", "strikeout"]).await;
+        game.reviews = vec![Some(Review { outcome: ReviewOutcome::Overturned, challenge_team_id: Some(119) })];
+        let preprocessed = game.preprocess(&PreprocessOptions::default());
+
+        assert!(preprocessed.contains("\"type\": \"Single\", \"review\": { \"outcome\": \"Overturned\", \"challenge_team_id\": 119 } }"), "{preprocessed}");
+        assert_eq!(preprocessed.matches("review").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn text_output() {
+        let game = game_from_fixtures(&["single"]).await;
+        let options = PreprocessOptions { formatter: Some(std::sync::Arc::new(TextFormatter)), ..Default::default() };
+
+        let text = game.preprocess(&options);
+        let json = game.preprocess(&PreprocessOptions::default());
+
+        assert!(text.contains("\nindex: 0, inning.number: 3, inning.top: true, type: Single\n"), "{text}");
+        assert!(text.contains("movements: [(runner: Mookie Betts, start_base: home, end_base: 1, is_out: false)]"), "{text}");
+        assert_eq!(text.lines().count(), json.lines().count());
+        // a play on its own has no index
+        assert!(game.plays[0].preprocess(&options).starts_with("inning.number: 3, inning.top: true, type: Single\nbatter: Mookie Betts"));
+    }
+
+    #[tokio::test]
+    async fn fielders_as_position_numbers() {
+        let path = format!("{}/tests/fixtures/plays/grounded_into_double_play_6_4_3.json", env!("CARGO_MANIFEST_DIR"));
+        let value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(Play::fielder_positions_from_value(&value), [Some(6), Some(4), Some(3)]);
+
+        let mut game = game_from_fixtures(&["grounded_into_double_play_6_4_3"]).await;
+        game.fielder_positions = vec![Play::fielder_positions_from_value(&value)];
+        let fielders = |game: &Game, fielders| {
+            let lines = game.to_value(&PreprocessOptions { fielders, ..Default::default() });
+            lines[2]["fielders"].clone()
+        };
+
+        assert_eq!(fielders(&game, FielderFormat::Names), serde_json::json!(["DJ LeMahieu", "Gleyber Torres", "Luke Voit"]));
+        assert_eq!(fielders(&game, FielderFormat::Positions), serde_json::json!([6, 4, 3]));
+        assert_eq!(fielders(&game, FielderFormat::Both)[2], serde_json::json!({ "position": 3, "name": "Luke Voit" }));
+
+        // games saved before positions were parsed have none
+        game.fielder_positions.clear();
+        assert_eq!(fielders(&game, FielderFormat::Positions), serde_json::json!([null, null, null]));
+    }
+
+    #[tokio::test]
+    async fn game_advisories_have_a_note() {
+        let game = game_from_fixtures(&["game_advisory"]).await;
+
+        let preprocessed = game.preprocess(&PreprocessOptions::default());
+
+        assert_eq!(preprocessed.lines().nth(2), Some("{ \"note\": \"Status Change - Delayed: Rain\" }"));
+    }
+
+    #[tokio::test]
+    async fn ejected_from_the_description() {
+        offline_api();
+        let path = format!("{}/tests/fixtures/plays/ejection.json", env!("CARGO_MANIFEST_DIR"));
+        let mut value = serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(path).unwrap()).unwrap();
+        value["playEvents"] = serde_json::json!([]);
+        value["result"]["description"] = "Third base coach Luis Rojas ejected by 3B umpire Chad Fairchild.".into();
+
+        let Play::Ejection { ejected, reason, .. } = Play::from_value(&value).await.unwrap() else { panic!("not an ejection") };
+        assert_eq!(ejected.as_deref(), Some("Third base coach Luis Rojas"));
+        assert_eq!(reason, None);
+    }
+
+    #[tokio::test]
+    async fn movement_lines_can_be_left_out() {
+        let game = game_from_fixtures(&["single", "sac_fly_with_one_runner"]).await;
+
+        let with_movements = game.preprocess(&PreprocessOptions::default());
+        let without_movements = game.preprocess(&PreprocessOptions { no_movements: true, ..Default::default() });
+
+        assert!(with_movements.contains("movements"));
+        assert!(!without_movements.contains("movements"));
+        // one movement line per play fewer, and the other lines are the same
+        assert_eq!(without_movements.lines().count(), with_movements.lines().count() - 2);
+        assert!(without_movements.lines().all(|line| with_movements.lines().any(|other| other == line)));
+    }
+
+    #[tokio::test]
+    async fn preprocessed_text_is_the_formatted_value() {
+        let game = game_from_fixtures(&["single", "sac_fly_with_one_runner"]).await;
+        let options = PreprocessOptions { descriptions: true, ..Default::default() };
+
+        let value = game.to_value(&options);
+        let preprocessed = game.preprocess(&options);
+
+        let lines = preprocessed.lines().collect::<Vec<&str>>();
+        assert_eq!(value.as_array().unwrap().len(), lines.len());
+        assert_eq!(format_json(&value[1], false), lines[1]);
+        assert_eq!(value[1]["index"], 0);
+        assert_eq!(value[1]["type"], "Single");
+        assert_eq!(value.as_array().unwrap().last().unwrap()["movements"][0]["end_base"], "home");
+    }
+
+    #[test]
+    fn alignment_is_only_emitted_when_requested() {
+        let play = |details: serde_json::Value| serde_json::json!({
+            "matchup": { "batter": { "id": 605141 } },
+            "playEvents": [{ "isPitch": true, "details": details, "hitData": { "launchSpeed": 101.5 } }],
+        });
+
+        let hit_data = HitData::from_value(&play(serde_json::json!({ "code": "X", "ifFieldingAlignment": "Infield shift" })));
+        assert_eq!(hit_data.alignment, Some([("ifFieldingAlignment".to_string(), "Infield shift".to_string())].into()));
+        assert!(hit_data.to_value(&PreprocessOptions::default()).get("alignment").is_none());
+        assert_eq!(hit_data.to_value(&PreprocessOptions { alignment: true, ..Default::default() })["alignment"]["ifFieldingAlignment"], "Infield shift");
+
+        let hit_data = HitData::from_value(&play(serde_json::json!({ "code": "X" })));
+        assert_eq!(hit_data.alignment, None);
+        assert!(hit_data.to_value(&PreprocessOptions { alignment: true, ..Default::default() })["alignment"].is_null());
+    }
+
+    #[tokio::test]
+    async fn keys_are_renamed_and_lowercased() {
+        let game = game_from_fixtures(&["single"]).await;
+        let options = PreprocessOptions {
+            descriptions: true,
+            lowercase: true,
+            key_names: SHORT_KEY_NAMES.iter().map(|(key, name)| (key.to_string(), name.to_string())).collect(),
+            ..Default::default()
+        };
+
+        let preprocessed = game.preprocess(&options);
+        let play_lines = preprocessed.lines().skip(1).collect::<Vec<&str>>();
+
+        assert!(play_lines[0].starts_with("{ \"i\": 0, \"inn\": { \"n\": 3, \"top\": true }, \"t\": \"single\", \"desc\": \"mookie betts singles"), "{}", play_lines[0]);
+        assert!(play_lines[1].starts_with("{ \"bat\": \"mookie betts\", \"pit\": \"gerrit cole\", \"hd\": { \"ev\": "), "{}", play_lines[1]);
+        // a string value that happens to be a key name is not renamed
+        assert_eq!(rename_keys(serde_json::json!({ "runner": "runner" }), &options.key_names), serde_json::json!({ "r": "runner" }));
+    }
+
+    /// Check that the last play parsed from the fixture with the test's name matches the pattern.
+    macro_rules! fixture_test {
+        ($name:ident, $pattern:pat $(if $guard:expr)?) => {
+            #[tokio::test]
+            async fn $name() {
+                let plays = plays_from_fixture(stringify!($name)).await;
+                let play = plays.last().unwrap();
+
+                assert!(matches!(play, $pattern $(if $guard)?), "{play:?}");
+            }
+        };
+    }
+
+    fixture_test!(groundout, Play::Groundout { batter, pitcher, fielders, movements, .. }
+        if batter == "Mookie Betts" && pitcher == "Gerrit Cole" && *fielders == ["Gleyber Torres", "Luke Voit"] && movements.len() == 1 && movements[0].is_out);
+    fixture_test!(bunt_groundout, Play::BuntGroundout { batter, fielders, .. } if batter == "Mookie Betts" && *fielders == ["Gerrit Cole"]);
+    fixture_test!(strikeout, Play::Strikeout { batter, pitcher, .. } if batter == "Mookie Betts" && pitcher == "Gerrit Cole");
+    fixture_test!(lineout, Play::Lineout { fielders, hit_data, .. } if *fielders == ["DJ LeMahieu"] && hit_data.exit_velocity == Some(101.5));
+    fixture_test!(bunt_lineout, Play::BuntLineout { fielders, .. } if *fielders == ["Gerrit Cole"]);
+    fixture_test!(flyout, Play::Flyout { fielders, hit_data, .. }
+        if *fielders == ["Aaron Judge"] && hit_data.launch_angle == Some(31.0) && hit_data.total_distance == Some(355.0));
+    fixture_test!(pop_out, Play::PopOut { fielders, .. } if *fielders == ["Gleyber Torres"]);
+    fixture_test!(bunt_pop_out, Play::BuntPopOut { fielders, .. } if *fielders == ["Gary Sanchez"]);
+    fixture_test!(forceout, Play::Forceout { fielders, movements, .. } if *fielders == ["Gleyber Torres"] && movements.len() == 2);
+    fixture_test!(fielders_choice_out, Play::FieldersChoiceOut { fielders, scoring_runner, .. }
+        if *fielders == ["Gleyber Torres"] && scoring_runner == "Corey Seager");
+    fixture_test!(double_play, Play::DoublePlay { fielders, .. } if *fielders == ["DJ LeMahieu", "Luke Voit"]);
+    fixture_test!(triple_play, Play::TriplePlay { fielders, movements, .. }
+        if *fielders == ["DJ LeMahieu", "Gleyber Torres", "Luke Voit"] && movements.iter().all(|movement| movement.is_out));
+    fixture_test!(runner_double_play, Play::RunnerDoublePlay { fielders, .. } if *fielders == ["Aaron Judge", "Gleyber Torres"]);
+    fixture_test!(runner_triple_play, Play::RunnerTriplePlay { fielders, .. } if *fielders == ["Gleyber Torres", "Luke Voit", "DJ LeMahieu"]);
+    fixture_test!(grounded_into_double_play, Play::GroundedIntoDoublePlay { batter, fielders, .. }
+        if batter == "Mookie Betts" && *fielders == ["DJ LeMahieu", "Luke Voit"]);
+    fixture_test!(grounded_into_double_play_6_4_3, Play::GroundedIntoDoublePlay { fielders, .. }
+        if *fielders == ["DJ LeMahieu", "Gleyber Torres", "Luke Voit"]);
+    fixture_test!(strikeout_double_play, Play::StrikeoutDoublePlay { fielders, .. } if *fielders == ["Gary Sanchez", "Gleyber Torres"]);
+    fixture_test!(pickoff, Play::Pickoff { base: 1, runner, fielders, .. } if runner == "Justin Turner" && *fielders == ["Luke Voit"]);
+    fixture_test!(pickoff_error, Play::PickoffError { base: 1, runner, movements, .. }
+        if runner == "Justin Turner" && movements[0].end_base == Some(2));
+    fixture_test!(caught_stealing, Play::CaughtStealing { base: 2, runner, fielders, .. } if runner == "Justin Turner" && *fielders == ["Gary Sanchez", "Gleyber Torres"]);
+    fixture_test!(pickoff_caught_stealing, Play::PickoffCaughtStealing { base: 2, runner, .. } if runner == "Justin Turner");
+    fixture_test!(wild_pitch, Play::WildPitch { pitcher, runner, .. } if pitcher == "Gerrit Cole" && runner == "Justin Turner");
+    fixture_test!(runner_out, Play::RunnerOut { runner, fielders, .. } if runner == "Justin Turner" && *fielders == ["Gleyber Torres"]);
+    fixture_test!(field_out, Play::FieldOut { fielder, runner, .. } if fielder == "Aaron Judge" && runner == "Justin Turner");
+    fixture_test!(batter_out, Play::BatterOut { batter, catcher, .. } if batter == "Mookie Betts" && catcher.as_deref() == Some("Gary Sanchez"));
+    fixture_test!(batter_out_without_credits, Play::BatterOut { batter, catcher: None, .. } if batter == "Mookie Betts");
+    fixture_test!(balk, Play::Balk { pitcher, movements, .. } if pitcher == "Gerrit Cole" && movements[0].start_base == Some(1));
+    fixture_test!(passed_ball, Play::PassedBall { pitcher, catcher, .. } if pitcher == "Gerrit Cole" && catcher == "Gary Sanchez");
+    fixture_test!(error, Play::Error { pitcher, catcher, .. } if pitcher == "Gerrit Cole" && catcher == "Gary Sanchez");
+    fixture_test!(single, Play::Single { batter, hit_data, movements, .. }
+        if batter == "Mookie Betts" && hit_data.exit_velocity == Some(101.2) && movements[0].start_base.is_none() && movements[0].end_base == Some(1));
+    fixture_test!(single_with_unnamed_runner, Play::Single { movements, .. } if movements.len() == 1 && movements[0].runner == "Mookie Betts");
+    fixture_test!(double, Play::Double { movements, .. } if movements[0].end_base == Some(2));
+    fixture_test!(triple, Play::Triple { movements, .. } if movements[0].end_base == Some(3));
+    fixture_test!(home_run, Play::HomeRun { hit_data, movements, .. } if hit_data.total_distance == Some(412.0) && movements[0].end_base == Some(4));
+    fixture_test!(walk, Play::Walk { batter, pitcher, .. } if batter == "Mookie Betts" && pitcher == "Gerrit Cole");
+    fixture_test!(intent_walk, Play::IntentWalk { batter, .. } if batter == "Mookie Betts");
+    fixture_test!(hit_by_pitch, Play::HitByPitch { batter, .. } if batter == "Mookie Betts");
+    fixture_test!(fielders_choice, Play::FieldersChoice { fielders, movements, .. } if *fielders == ["Gleyber Torres"] && movements.len() == 2);
+    fixture_test!(catcher_interference, Play::CatcherInterference { fielders, .. } if *fielders == ["Gary Sanchez"]);
+    fixture_test!(batter_interference, Play::BatterInterference { batter, movements, .. } if batter == "Mookie Betts" && movements[0].is_out);
+    fixture_test!(fan_interference, Play::FanInterference { batter, hit_data, movements, .. }
+        if batter == "Mookie Betts" && hit_data.exit_velocity == Some(105.4) && movements[0].end_base == Some(2));
+    fixture_test!(stolen_base, Play::StolenBase { base: 2, runner, other_steals, .. } if runner == "Justin Turner" && other_steals.is_empty());
+    fixture_test!(double_steal, Play::StolenBase { base: 2, runner, other_steals, movements, .. }
+        if runner == "Justin Turner" && matches!(&other_steals[..], [Steal { runner, base: 3 }] if runner == "Mookie Betts") && movements.len() == 2);
+    // the event does not name the base, so it is the base the runner ended up on
+    fixture_test!(stolen_base_without_base, Play::StolenBase { base: 3, runner, .. } if runner == "Justin Turner");
+    fixture_test!(sac_fly, Play::SacFly { fielders, scoring_runner, .. } if *fielders == ["Aaron Judge"] && scoring_runner.as_deref() == Some("Corey Seager"));
+    fixture_test!(sac_fly_double_play, Play::SacFlyDoublePlay { fielders, scoring_runner, .. }
+        if *fielders == ["Aaron Judge", "Luke Voit"] && scoring_runner.as_deref() == Some("Corey Seager"));
+    fixture_test!(sac_bunt, Play::SacBunt { fielders, runner, .. } if *fielders == ["Luke Voit"] && runner.as_deref() == Some("Justin Turner"));
+    fixture_test!(sac_bunt_double_play, Play::SacBuntDoublePlay { fielders, runner, .. }
+        if *fielders == ["Luke Voit", "DJ LeMahieu"] && runner.as_deref() == Some("Justin Turner"));
+    fixture_test!(sac_fly_with_one_runner, Play::SacFly { scoring_runner: None, .. });
+    fixture_test!(field_error, Play::FieldError { fielders, .. } if *fielders == ["Gleyber Torres"]);
+    fixture_test!(defensive_indifference, Play::DefensiveIndifference { runner, .. } if runner == "Justin Turner");
+    fixture_test!(pickoff_attempt, Play::PickoffAttempt { base: 1, pitcher, runner, .. } if pitcher == "Gerrit Cole" && runner == "Justin Turner");
+    fixture_test!(game_advisory, Play::GameAdvisory { inning: Inning { number: 3, top: true }, description: Some(description) }
+        if description == "Status Change - Delayed: Rain");
+    fixture_test!(ejection, Play::Ejection { ejected, reason, movements, .. }
+        if ejected.as_deref() == Some("Aaron Boone") && reason.as_deref() == Some("arguing balls and strikes") && movements.is_empty());
+
+    #[tokio::test]
+    async fn pitching_change() {
+        let plays = plays_from_fixture("pitching_change").await;
+
+        assert!(matches!(&plays[..], [Play::PitchingChange { new_pitcher, .. }, Play::Single { pitcher, .. }]
+            if new_pitcher == "Chad Green" && pitcher == "Chad Green"), "{plays:?}");
+    }
+
+    #[tokio::test]
+    async fn mound_visit() {
+        let plays = plays_from_fixture("mound_visit").await;
+
+        assert!(matches!(&plays[..], [Play::MoundVisit { .. }, Play::Walk { .. }]), "{plays:?}");
+    }
+
+    /// Every play type, as [`Play::play_type`] writes it in the output.
+    const PREPROCESSED_PLAY_TYPES: &[&str] = &[
+        "Groundout",
+        "Bunt Groundout",
+        "Strikeout",
+        "Lineout",
+        "Bunt Lineout",
+        "Flyout",
+        "Pop Out",
+        "Bunt Pop Out",
+        "Forceout",
+        "Fielders Choice Out",
+        "Double Play",
+        "Triple Play",
+        "Runner Double Play",
+        "Runner Triple Play",
+        "Grounded Into Double Play",
+        "Strikeout Double Play",
+        "Pickoff",
+        "Pickoff Error",
+        "Caught Stealing",
+        "Pickoff Caught Stealing",
+        "Wild Pitch",
+        "Runner Out",
+        "Field Out",
+        "Batter Out",
+        "Balk",
+        "Passed Ball",
+        "Error",
+        "Single",
+        "Double",
+        "Triple",
+        "Home Run",
+        "Walk",
+        "Intent Walk",
+        "Hit By Pitch",
+        "Fielders Choice",
+        "Catcher Interference",
+        "Batter Interference",
+        "Fan Interference",
+        "Stolen Base",
+        "Sac Fly",
+        "Sac Fly Double Play",
+        "Sac Bunt",
+        "Sac Bunt Double Play",
+        "Field Error",
+        "Defensive Indifference",
+        "Pickoff Attempt",
+        "Pitching Change",
+        "Mound Visit",
+        "Game Advisory",
+        "Automatic Runner",
+        "Ejection",
+    ];
+
+    #[tokio::test]
+    async fn play_types_round_trip() {
+        offline_api();
+        let mut play_types = std::collections::BTreeSet::new();
+        for path in glob::glob(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/plays/*.json")).unwrap() {
+            let mut value = serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(path.unwrap()).unwrap()).unwrap();
+            let play = Play::from_value(&value).await.unwrap();
+
+            value["result"]["event"] = play.play_type().into();
+            let reparsed = Play::from_value(&value).await.unwrap();
+
+            assert_eq!(format!("{reparsed:?}"), format!("{play:?}"));
+            play_types.insert(play.play_type());
+        }
+        // no fixture is one of these, since they come from play events or are added to extra innings, but they can be
+        // parsed from a play of their type all the same
+        let path = format!("{}/tests/fixtures/plays/single.json", env!("CARGO_MANIFEST_DIR"));
+        let mut value = serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(path).unwrap()).unwrap();
+        for play_type in ["Pitching Change", "Mound Visit", "Automatic Runner"] {
+            value["result"]["event"] = play_type.into();
+            let play = Play::from_value(&value).await.unwrap();
+
+            assert_eq!(play.play_type(), play_type);
+            play_types.insert(play.play_type());
+        }
+
+        assert_eq!(play_types, PREPROCESSED_PLAY_TYPES.iter().copied().collect());
+    }
+
+    #[tokio::test]
+    async fn play_type_filters() {
+        let plays = plays_from_fixture("pitching_change").await;
+        let options = PreprocessOptions {
+            include_types: Some(vec!["Single".to_string(), "Home Run".to_string()]),
+            ..Default::default()
+        };
+        assert!(!options.includes(&plays[0]) && options.includes(&plays[1]));
+
+        let options = PreprocessOptions {
+            exclude_types: vec!["PitchingChange".to_string()],
+            ..Default::default()
+        };
+        assert!(!options.includes(&plays[0]) && options.includes(&plays[1]));
+    }
+
+    #[tokio::test]
+    async fn inning_filters() {
+        let mut game = game_from_fixtures(&["single", "strikeout"]).await;
+        game.plays.push(Play::MoundVisit { inning: Inning { number: 7, top: false }, description: None });
+        game.plays.push(Play::GameAdvisory { inning: Inning { number: 9, top: true }, description: None });
+
+        let indices = |min_inning, max_inning| {
+            let lines = game.to_value(&PreprocessOptions { min_inning, max_inning, ..Default::default() });
+            lines.as_array().unwrap().iter().filter_map(|line| line["index"].as_u64()).collect::<Vec<u64>>()
+        };
+
+        // the fixtures are in the 3rd inning
+        assert_eq!(indices(None, None), [0, 1, 2, 3]);
+        assert_eq!(indices(Some(7), None), [2, 3]);
+        assert_eq!(indices(Some(4), Some(8)), [2]);
+        assert_eq!(indices(None, Some(3)), [0, 1]);
+    }
+
+    #[tokio::test]
+    async fn feed_without_plays_is_skipped() {
+        let game_data = serde_json::json!({ "liveData": { "plays": {} } });
+
+        let result = Game::plays_from_feed(&game_data, 0, true, &ParseOptions::default()).await;
+
+        assert!(matches!(result, Err(GameError::Parse(e)) if e == "No plays"));
+    }
+
+    #[test]
+    fn unknown_base_is_an_error() {
+        assert_eq!(base_value_to_option_u8(&serde_json::json!(null)), Ok(None));
+        assert_eq!(base_value_to_option_u8(&serde_json::json!("2B")), Ok(Some(2)));
+        assert_eq!(base_value_to_option_u8(&serde_json::json!("score")), Ok(Some(4)));
+        assert!(base_value_to_option_u8(&serde_json::json!("5B")).is_err());
+        assert!(base_value_to_option_u8(&serde_json::json!(2)).is_err());
+
+        let movement = Movement::from_runner_and_value("Mookie Betts".to_string(), &serde_json::json!({ "start": "1B", "end": "home plate" }));
+        assert!(movement.is_err());
+    }
+
+    /// Every play type `Play::from_value` knows, so generated plays reach each parser.
+    const PLAY_TYPES: &[&str] = &[
+        "Groundout",
+        "Bunt Groundout",
+        "Strikeout",
+        "Lineout",
+        "Bunt Lineout",
+        "Flyout",
+        "Pop Out",
+        "Bunt Pop Out",
+        "Forceout",
+        "Fielders Choice Out",
+        "Catcher Interference",
+        "Batter Interference",
+        "Fan Interference",
+        "Double Play",
+        "Triple Play",
+        "Runner Double Play",
+        "Runner Triple Play",
+        "Grounded Into DP",
+        "Strikeout Double Play",
+        "Pickoff 1B",
+        "Pickoff 2B",
+        "Pickoff 3B",
+        "Pickoff Error 1B",
+        "Pickoff Error 2B",
+        "Pickoff Error 3B",
+        "Caught Stealing",
+        "Caught Stealing 2B",
+        "Caught Stealing 3B",
+        "Caught Stealing Home",
+        "Pickoff Caught Stealing 1B",
+        "Pickoff Caught Stealing 2B",
+        "Pickoff Caught Stealing 3B",
+        "Pickoff Caught Stealing Home",
+        "Wild Pitch",
+        "Runner Out",
+        "Field Out",
+        "Batter Out",
+        "Balk",
+        "Passed Ball",
+        "Error",
+        "Single",
+        "Double",
+        "Triple",
+        "Home Run",
+        "Walk",
+        "Intent Walk",
+        "Hit By Pitch",
+        "Fielders Choice",
+        "Stolen Base",
+        "Stolen Base 1B",
+        "Stolen Base 2B",
+        "Stolen Base 3B",
+        "Stolen Base Home",
+        "Sac Fly",
+        "Sac Fly Double Play",
+        "Sac Bunt",
+        "Sac Bunt Double Play",
+        "Field Error",
+        "Defensive Indiff",
+        "Defensive Indifference",
+        "Pickoff Attempt 1B",
+        "Pickoff Attempt 2B",
+        "Pickoff Attempt 3B",
+        "Pitching Change",
+        "Mound Visit",
+        "Game Advisory",
+        "Automatic Runner",
+        "Ejection",
+    ];
+
+    /// A JSON value that is either the given value, `null`, or of the wrong type.
+    fn maybe_broken(value: impl Strategy<Value = serde_json::Value>) -> impl Strategy<Value = serde_json::Value> {
+        prop_oneof![
+            6 => value,
+            1 => Just(serde_json::Value::Null),
+            1 => Just(serde_json::json!([])),
+            1 => Just(serde_json::json!(0)),
+        ]
+    }
+
+    /// An object with the given fields, each of which may be missing.
+    fn object_with_missing_fields(fields: Vec<(&'static str, BoxedStrategy<serde_json::Value>)>) -> BoxedStrategy<serde_json::Value> {
+        fields
+            .into_iter()
+            .map(|(key, value)| proptest::option::weighted(0.85, value).prop_map(move |value| (key, value)))
+            .collect::<Vec<_>>()
+            .prop_map(|fields| serde_json::Value::Object(
+                fields.into_iter().filter_map(|(key, value)| Some((key.to_string(), value?))).collect(),
+            ))
+            .boxed()
+    }
+
+    fn person() -> BoxedStrategy<serde_json::Value> {
+        object_with_missing_fields(vec![
+            ("id", maybe_broken((1u64..1_000_000).prop_map(serde_json::Value::from)).boxed()),
+            ("fullName", maybe_broken("[A-Z][a-z]{2,8} [A-Z][a-z]{2,8}".prop_map(serde_json::Value::from)).boxed()),
+        ])
+    }
+
+    fn base() -> BoxedStrategy<serde_json::Value> {
+        maybe_broken(prop_oneof!["1B", "2B", "3B", "4B", "score", "5B"].prop_map(serde_json::Value::from)).boxed()
+    }
+
+    fn runner() -> BoxedStrategy<serde_json::Value> {
+        object_with_missing_fields(vec![
+            ("details", object_with_missing_fields(vec![
+                ("runner", person()),
+                ("fielder", person()),
+                ("pitcher", person()),
+            ])),
+            ("movement", object_with_missing_fields(vec![
+                ("start", base()),
+                ("end", base()),
+                ("isOut", maybe_broken(any::<bool>().prop_map(serde_json::Value::from)).boxed()),
+            ])),
+            ("credits", maybe_broken(proptest::collection::vec(
+                object_with_missing_fields(vec![("player", person())]),
+                0..3,
+            ).prop_map(serde_json::Value::from)).boxed()),
+        ])
+    }
+
+    /// A plausible play from a live feed, with any of its fields missing, `null`, or of the wrong type.
+    fn play() -> BoxedStrategy<serde_json::Value> {
+        object_with_missing_fields(vec![
+            ("result", object_with_missing_fields(vec![
+                ("event", maybe_broken(prop_oneof![
+                    9 => proptest::sample::select(PLAY_TYPES).prop_map(serde_json::Value::from),
+                    1 => "[A-Za-z ]{0,20}".prop_map(serde_json::Value::from),
+                ]).boxed()),
+                ("description", maybe_broken("[A-Za-z .]{0,40}".prop_map(serde_json::Value::from)).boxed()),
+            ])),
+            ("about", object_with_missing_fields(vec![
+                ("inning", maybe_broken((1u64..20).prop_map(serde_json::Value::from)).boxed()),
+                ("isTopInning", maybe_broken(any::<bool>().prop_map(serde_json::Value::from)).boxed()),
+            ])),
+            ("matchup", object_with_missing_fields(vec![
+                ("batter", person()),
+                ("pitcher", person()),
+            ])),
+            ("runners", maybe_broken(proptest::collection::vec(runner(), 0..4).prop_map(serde_json::Value::from)).boxed()),
+            ("playEvents", maybe_broken(proptest::collection::vec(
+                object_with_missing_fields(vec![
+                    ("isPitch", maybe_broken(any::<bool>().prop_map(serde_json::Value::from)).boxed()),
+                    ("details", object_with_missing_fields(vec![
+                        ("eventType", maybe_broken(prop_oneof!["pitching_substitution", "mound_visit", "ejection", "ball"].prop_map(serde_json::Value::from)).boxed()),
+                    ])),
+                    ("player", person()),
+                ]),
+                0..3,
+            ).prop_map(serde_json::Value::from)).boxed()),
+        ])
+    }
+
+    proptest! {
+        #[test]
+        fn play_from_value_never_panics(value in play()) {
+            offline_api();
+
+            let _ = futures::executor::block_on(Play::all_from_value(&value));
+        }
     }
 }
+
@@ -0,0 +1,164 @@
+use crate::model::Play;
+
+/// The number of runs expected to score in the rest of a half-inning from each of the 24 base-out states.
+#[derive(Debug, Clone)]
+pub struct RunExpectancy {
+    /// Indexed by the occupied bases (bit 0 for first, bit 1 for second, bit 2 for third), then by the number of outs.
+    values: [[f32; 3]; 8],
+}
+
+impl Default for RunExpectancy {
+    /// A typical modern MLB run expectancy matrix (2010-2015).
+    fn default() -> Self {
+        Self {
+            values: [
+                [0.481, 0.254, 0.098], // ---
+                [0.859, 0.509, 0.224], // 1--
+                [1.100, 0.664, 0.319], // -2-
+                [1.437, 0.884, 0.429], // 12-
+                [1.356, 0.950, 0.353], // --3
+                [1.798, 1.140, 0.478], // 1-3
+                [1.920, 1.352, 0.570], // -23
+                [2.282, 1.520, 0.736], // 123
+            ],
+        }
+    }
+}
+
+impl RunExpectancy {
+    /// Load a matrix from a JSON file containing 8 rows of 3 numbers, one row per base state
+    /// (in the order `---`, `1--`, `-2-`, `12-`, `--3`, `1-3`, `-23`, `123`) and one column per number of outs.
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read run expectancy matrix: {e}"))?;
+        let values = serde_json::from_str(&contents).map_err(|e| format!("Invalid run expectancy matrix: {e}"))?;
+
+        Ok(Self { values })
+    }
+
+    /// The runs expected from the given base-out state, which is 0 once the half-inning is over.
+    fn expected_runs(&self, bases: &BaseState, outs: usize) -> f32 {
+        if outs >= 3 {
+            return 0.0;
+        }
+
+        self.values[bases.index()][outs]
+    }
+
+    /// The change in run expectancy of each play, plus the runs it scored, or `None` for plays that do not change the base-out state.
+    /// The base-out state is reconstructed from the runners' movements, starting afresh each half-inning.
+    pub fn re24s(&self, plays: &[Play]) -> Vec<Option<f32>> {
+        let mut half_inning = None;
+        let mut bases = BaseState::default();
+        let mut outs = 0;
+
+        plays
+            .iter()
+            .map(|play| {
+                let inning = play.inning();
+                if half_inning != Some((inning.number, inning.top)) {
+                    half_inning = Some((inning.number, inning.top));
+                    bases = BaseState::default();
+                    outs = 0;
+                }
+
+                if let Play::AutomaticRunner { runner, .. } = play {
+                    bases.runners[1] = Some(runner.clone());
+                    return None;
+                }
+                if play.movements().is_empty() {
+                    return None;
+                }
+
+                let start = self.expected_runs(&bases, outs);
+                let mut runs = 0;
+                for movement in play.movements() {
+                    bases.remove(&movement.runner);
+                    if movement.is_out {
+                        outs += 1;
+                    } else {
+                        match movement.end_base {
+                            Some(base @ 1..=3) => bases.runners[base as usize - 1] = Some(movement.runner.clone()),
+                            Some(4) => runs += 1,
+                            _ => {},
+                        }
+                    }
+                }
+                let end = self.expected_runs(&bases, outs);
+
+                Some(end - start + runs as f32)
+            })
+            .collect()
+    }
+}
+
+/// The runners on first, second and third base.
+#[derive(Debug, Default)]
+struct BaseState {
+    runners: [Option<String>; 3],
+}
+
+impl BaseState {
+    fn remove(&mut self, runner: &str) {
+        for base in self.runners.iter_mut() {
+            if base.as_deref() == Some(runner) {
+                *base = None;
+            }
+        }
+    }
+
+    fn index(&self) -> usize {
+        self.runners
+            .iter()
+            .enumerate()
+            .filter(|(_, runner)| runner.is_some())
+            .map(|(base, _)| 1 << base)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{HitData, Inning, Movement};
+
+    fn movement(runner: &str, start_base: Option<u8>, end_base: Option<u8>, is_out: bool) -> Movement {
+        Movement { runner: runner.to_string(), start_base, end_base, is_out }
+    }
+
+    fn play(movements: Vec<Movement>) -> Play {
+        Play::Single {
+            inning: Inning { number: 1, top: true },
+            description: None,
+            hit_data: HitData::default(),
+            batter: "Batter".to_string(),
+            pitcher: "Pitcher".to_string(),
+            movements,
+        }
+    }
+
+    #[test]
+    fn solo_home_run_is_worth_one_run() {
+        let plays = [play(vec![movement("Batter", None, Some(4), false)])];
+
+        let re24s = RunExpectancy::default().re24s(&plays);
+
+        assert_eq!(re24s, [Some(1.0)]);
+    }
+
+    #[test]
+    fn state_carries_through_the_half_inning() {
+        let plays = [
+            play(vec![movement("A", None, Some(1), false)]),
+            play(vec![movement("B", None, None, true), movement("A", Some(1), Some(2), false)]),
+            play(vec![movement("C", None, None, true)]),
+            play(vec![movement("D", None, None, true)]),
+        ];
+
+        let re24s = RunExpectancy::default().re24s(&plays);
+
+        let expected = [0.859 - 0.481, 0.664 - 0.859, 0.319 - 0.664, 0.0 - 0.319];
+        for (re24, expected) in re24s.iter().zip(expected) {
+            assert!((re24.unwrap() - expected).abs() < 1e-6, "{re24s:?}");
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The sport id of Major League Baseball.
+pub const MLB_SPORT_ID: u32 = 1;
+
+/// The directory raw games of the given sport are saved in. MLB games are saved directly in `data`,
+/// other sports in `data/sport_{sport_id}`.
+pub fn data_dir(sport_id: u32) -> String {
+    if sport_id == MLB_SPORT_ID {
+        "data".to_string()
+    } else {
+        format!("data/sport_{sport_id}")
+    }
+}
+
+/// Write `contents` to `path` by writing a temporary file next to it and renaming it into place,
+/// so readers never see a partially-written file.
+pub fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+/// Take an exclusive advisory lock on the directory `dir` (through the file `{dir}/.lock`), which is held until
+/// the returned file is dropped. Fails if another process holds it.
+pub fn lock_dir(dir: impl AsRef<Path>) -> Result<std::fs::File, String> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+
+    let lock_path = dir.join(".lock");
+    let lock_file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| format!("Failed to open {}: {e}", lock_path.display()))?;
+    match lock_file.try_lock() {
+        Ok(()) => Ok(lock_file),
+        Err(std::fs::TryLockError::WouldBlock) => Err(format!("{} is in use by another command", dir.display())),
+        Err(std::fs::TryLockError::Error(e)) => Err(format!("Failed to lock {}: {e}", lock_path.display())),
+    }
+}
+
+/// The SHA-256 hash of `contents`, in hex.
+pub fn content_hash(contents: impl AsRef<[u8]>) -> String {
+    use sha2::Digest;
+
+    sha2::Sha256::digest(contents).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The file format games are saved in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameFormat {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl GameFormat {
+    pub const ALL: [GameFormat; 2] = [GameFormat::Json, GameFormat::MessagePack];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            GameFormat::Json => "json",
+            GameFormat::MessagePack => "msgpack",
+        }
+    }
+
+    /// The format of the game saved at `path`, from its extension (before the `.gz` of a compressed game).
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref();
+        let path = if is_compressed(path) { path.with_extension("") } else { path.to_path_buf() };
+
+        let extension = path.extension()?.to_str()?;
+        GameFormat::ALL.into_iter().find(|format| format.extension() == extension)
+    }
+}
+
+/// Whether the saved game at `path` is gzipped (e.g. `{game_pk}.json.gz`).
+pub fn is_compressed(path: impl AsRef<Path>) -> bool {
+    path.as_ref().extension().is_some_and(|extension| extension == "gz")
+}
+
+/// `path` without any of its extensions, e.g. `data/2024/147/775296` for `data/2024/147/775296.json.gz`.
+pub fn without_extensions(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => path.with_file_name(name.split('.').next().unwrap()),
+        None => path.to_path_buf(),
+    }
+}
+
+/// The game pk of the game saved at `path`, from its file name.
+pub fn game_pk_from_path(path: impl AsRef<Path>) -> Option<usize> {
+    without_extensions(path).file_name()?.to_str()?.parse().ok()
+}
+
+/// Read the saved game at `path`, decompressing it if it is gzipped.
+pub fn read_game_file(path: impl AsRef<Path>) -> std::io::Result<Vec<u8>> {
+    let path = path.as_ref();
+    let contents = std::fs::read(path)?;
+    if !is_compressed(path) {
+        return Ok(contents);
+    }
+
+    let mut decompressed = Vec::new();
+    std::io::Read::read_to_end(&mut flate2::read::GzDecoder::new(contents.as_slice()), &mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Write a game to `path` atomically, compressing it if `path` is that of a gzipped game.
+pub fn write_game_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if !is_compressed(path) {
+        return write_atomic(path, contents);
+    }
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(contents.as_ref())?;
+    write_atomic(path, encoder.finish()?)
+}
+
+/// A raw API response a game is parsed from, which can be saved next to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawResponse {
+    Feed,
+    Boxscore,
+}
+
+impl RawResponse {
+    pub const ALL: [RawResponse; 2] = [RawResponse::Feed, RawResponse::Boxscore];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RawResponse::Feed => "feed",
+            RawResponse::Boxscore => "boxscore",
+        }
+    }
+}
+
+/// Where the raw response of the game saved at `game_path` is saved: next to it, e.g. `data/2024/147/775296.feed.json`.
+pub fn raw_response_path(game_path: impl AsRef<Path>, response: RawResponse) -> PathBuf {
+    let path = without_extensions(game_path);
+    let name = format!("{}.{}.json", path.file_name().unwrap().to_string_lossy(), response.name());
+    path.with_file_name(name)
+}
+
+/// Whether `path` is that of a raw response (e.g. `775296.feed.json`) rather than of a game.
+pub fn is_raw_response(path: impl AsRef<Path>) -> bool {
+    let name = path.as_ref().file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    RawResponse::ALL.iter().any(|response| name.split('.').nth(1) == Some(response.name()))
+}
+
+/// The paths of the saved games matching `pattern`, a glob pattern without the file extension
+/// (e.g. `data/*/*/*`), in any format, compressed or not. Files not named after a game pk (e.g. `schedule.json`)
+/// and raw responses are left out.
+pub fn glob_games(pattern: &str) -> Vec<PathBuf> {
+    GameFormat::ALL
+        .into_iter()
+        .flat_map(|format| [format!("{pattern}.{}", format.extension()), format!("{pattern}.{}.gz", format.extension())])
+        .flat_map(|pattern| glob::glob(&pattern).unwrap())
+        .filter_map(|game_path| game_path.ok())
+        .filter(|game_path| game_pk_from_path(game_path).is_some() && !is_raw_response(game_path))
+        .collect()
+}
+
+/// Where the schedule of the given season is cached: `{data_dir}/{season}/schedule.json`.
+pub fn schedule_path(sport_id: u32, season: u16) -> PathBuf {
+    Path::new(&data_dir(sport_id)).join(season.to_string()).join("schedule.json")
+}
+
+/// A file that many threads write whole chunks (e.g. preprocessed games) to, without interleaving them.
+/// If `sorted`, chunks are written in the order of their indices rather than as they arrive, holding back
+/// those that arrive early.
+pub struct SharedWriter {
+    sorted: bool,
+    state: std::sync::Mutex<SharedWriterState>,
+}
+
+struct SharedWriterState {
+    writer: std::io::BufWriter<std::fs::File>,
+    /// The index of the next chunk to write, if sorted.
+    next_index: usize,
+    /// The chunks that arrived before the next chunk, by index, if sorted.
+    pending: std::collections::BTreeMap<usize, String>,
+}
+
+impl SharedWriter {
+    pub fn create(path: impl AsRef<Path>, sorted: bool) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        Ok(Self {
+            sorted,
+            state: std::sync::Mutex::new(SharedWriterState {
+                writer: std::io::BufWriter::new(std::fs::File::create(path)?),
+                next_index: 0,
+                pending: Default::default(),
+            }),
+        })
+    }
+
+    /// Write the chunk with the given index. If sorted, every index from 0 must be written exactly once.
+    pub fn write(&self, index: usize, chunk: String) -> std::io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !self.sorted {
+            return state.writer.write_all(chunk.as_bytes());
+        }
+
+        state.pending.insert(index, chunk);
+        loop {
+            let next_index = state.next_index;
+            let Some(chunk) = state.pending.remove(&next_index) else {
+                return Ok(());
+            };
+            state.writer.write_all(chunk.as_bytes())?;
+            state.next_index += 1;
+        }
+    }
+
+    /// Flush everything written to the file.
+    pub fn finish(self) -> std::io::Result<()> {
+        let mut state = self.state.into_inner().unwrap();
+        assert!(state.pending.is_empty(), "chunks {:?} were written out of order", state.pending.keys());
+
+        state.writer.flush()
+    }
+}